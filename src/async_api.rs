@@ -0,0 +1,202 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use eventual::{self, Async, AsyncError};
+use futures_core::Stream;
+
+use guided::GlobalTarget;
+use handle::VehicleHandle;
+use param::ParamError;
+use state::{AttributeValue, ListenerOptions};
+use vehicle::{LocationLocal, VehicleMode};
+
+// How many attribute updates a stream buffers before dropping the oldest.
+const STREAM_CAPACITY: usize = 64;
+
+struct CommandState<T, E> {
+    result: Option<Result<T, E>>,
+    waker: Option<Waker>,
+}
+
+// A `std::future::Future` that resolves when a vehicle command completes.
+// The `eventual` future it wraps is never polled or waited on: its receive
+// callback stores the result and wakes the task from whichever thread
+// resolves the command, usually the connection's event loop.
+pub struct CommandFuture<T, E = ()> {
+    state: Arc<Mutex<CommandState<T, E>>>,
+}
+
+impl<T: Send + 'static> CommandFuture<T> {
+    pub fn new(future: eventual::Future<T, ()>) -> CommandFuture<T> {
+        CommandFuture::with_error(future, ())
+    }
+}
+
+impl<T: Send + 'static, E: Send + 'static> CommandFuture<T, E> {
+    // Like `new`, but keeps the command's error. `aborted` is returned if
+    // the command is dropped without completing, as when the vehicle thread
+    // exits.
+    pub fn with_error(future: eventual::Future<T, E>, aborted: E) -> CommandFuture<T, E> {
+        let state = Arc::new(Mutex::new(CommandState {
+            result: None,
+            waker: None,
+        }));
+
+        let state_closure = state.clone();
+        future.receive(move |result| {
+            let mut state = state_closure.lock().unwrap();
+            state.result = Some(result.map_err(|e| {
+                match e {
+                    AsyncError::Failed(e) => e,
+                    AsyncError::Aborted => aborted,
+                }
+            }));
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        CommandFuture { state: state }
+    }
+}
+
+impl<T, E> Future for CommandFuture<T, E> {
+    type Output = Result<T, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<T, E>> {
+        let mut state = self.state.lock().unwrap();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+struct StreamState {
+    queue: VecDeque<AttributeValue>,
+    waker: Option<Waker>,
+    // The listener id, once the vehicle thread has added it.
+    id: Option<usize>,
+}
+
+// A stream of updates to a vehicle attribute. The listener is removed when
+// the stream is dropped.
+pub struct AttributeStream {
+    state: Arc<Mutex<StreamState>>,
+    handle: VehicleHandle,
+}
+
+impl Stream for AttributeStream {
+    type Item = AttributeValue;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<AttributeValue>> {
+        let mut state = self.state.lock().unwrap();
+        match state.queue.pop_front() {
+            Some(value) => Poll::Ready(Some(value)),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for AttributeStream {
+    fn drop(&mut self) {
+        // Calls run in order, so the listener has been added by the time
+        // this runs.
+        let state = self.state.clone();
+        self.handle.post(move |v| {
+            if let Some(id) = state.lock().unwrap().id {
+                v.remove_attribute_listener(id);
+            }
+        });
+    }
+}
+
+// An asynchronous interface to a vehicle running on its own thread. Futures
+// and streams returned here are `Send` and can be driven by any executor.
+// Calls are queued for the vehicle thread without waiting for it, so they
+// never block the caller.
+#[derive(Clone)]
+pub struct AsyncVehicle {
+    handle: VehicleHandle,
+}
+
+impl AsyncVehicle {
+    pub fn new(handle: VehicleHandle) -> AsyncVehicle {
+        AsyncVehicle { handle: handle }
+    }
+
+    // The blocking handle this vehicle wraps.
+    pub fn handle(&self) -> &VehicleHandle {
+        &self.handle
+    }
+
+    pub fn set_mode(&self, mode: VehicleMode) -> CommandFuture<()> {
+        CommandFuture::new(self.handle.set_mode(mode))
+    }
+
+    pub fn arm(&self) -> CommandFuture<()> {
+        CommandFuture::new(self.handle.arm())
+    }
+
+    pub fn takeoff(&self, target_alt: f32) -> CommandFuture<()> {
+        CommandFuture::new(self.handle.takeoff(target_alt))
+    }
+
+    pub fn goto(&self, target: LocationLocal) -> CommandFuture<()> {
+        CommandFuture::new(self.handle.goto(target))
+    }
+
+    pub fn goto_global<T: Into<GlobalTarget>>(&self, target: T, radius: f32) -> CommandFuture<()> {
+        CommandFuture::new(self.handle.goto_global(target, radius))
+    }
+
+    pub fn wait_alt(&self, target_alt: f32) -> CommandFuture<()> {
+        CommandFuture::new(self.handle.wait_alt(target_alt))
+    }
+
+    pub fn set_parameter(&self, name: &str, value: f32) -> CommandFuture<(), ParamError> {
+        CommandFuture::with_error(self.handle.set_parameter(name, value), ParamError::NoReply)
+    }
+
+    // Streams updates to `attr`, as with `Vehicle::add_attribute_listener`.
+    pub fn attribute_stream(&self, attr: &str, options: ListenerOptions) -> AttributeStream {
+        let state = Arc::new(Mutex::new(StreamState {
+            queue: VecDeque::new(),
+            waker: None,
+            id: None,
+        }));
+
+        let attr: String = attr.into();
+        let state_closure = state.clone();
+        let state_id = state.clone();
+        self.handle.post(move |v| {
+            let id = v.add_attribute_listener(&attr,
+                                              options,
+                                              Box::new(move |_, value| {
+                                                  let mut state = state_closure.lock().unwrap();
+                                                  if state.queue.len() >= STREAM_CAPACITY {
+                                                      state.queue.pop_front();
+                                                  }
+                                                  state.queue.push_back(value.clone());
+                                                  if let Some(waker) = state.waker.take() {
+                                                      waker.wake();
+                                                  }
+                                              }));
+            state_id.lock().unwrap().id = Some(id);
+        });
+
+        AttributeStream {
+            state: state,
+            handle: self.handle.clone(),
+        }
+    }
+}
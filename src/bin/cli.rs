@@ -0,0 +1,265 @@
+extern crate dronekit;
+extern crate eventual;
+
+// An interactive ground station for debugging vehicles over a terminal.
+//
+//     cli [HOST:PORT]
+//
+// Type `help` for a list of commands.
+
+use dronekit::connect;
+use dronekit::handle::VehicleHandle;
+use dronekit::mission;
+use dronekit::vehicle::{LocationLocal, VehicleMode};
+use dronekit::param::ParamError;
+use eventual::{Async, AsyncError, Future};
+
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::net::SocketAddr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const HELP: &'static str = "\
+commands:
+  mode NAME                 switch flight mode (GUIDED, LOITER, RTL, ...)
+  arm | disarm
+  takeoff ALT               take off to ALT meters
+  goto X Y Z                fly to a local NED position in meters
+  param show [PATTERN]      list cached parameters matching PATTERN
+  param set NAME VALUE
+  param fetch NAME          re-read a parameter from the vehicle
+  wp list                   download and print the mission
+  wp load FILE              upload a QGC WPL 110 waypoint file
+  wp save FILE              download the mission to a waypoint file
+  status                    print vehicle state
+  stats                     print link statistics and current rates
+  watch MSG [SECONDS]       print messages by name or id (default 5s)
+  help | quit";
+
+fn wait<T: Send + 'static>(future: Future<T, ()>) -> Result<T, String> {
+    future.await().map_err(|_| "failed".into())
+}
+
+fn wait_set(future: Future<(), ParamError>) -> Result<(), String> {
+    future.await().map_err(|e| {
+        match e {
+            AsyncError::Failed(e) => e.to_string(),
+            AsyncError::Aborted => "failed".into(),
+        }
+    })
+}
+
+fn status(handle: &VehicleHandle) -> String {
+    handle.with(|v| {
+        let firmware = v.firmware;
+        let mode = v.mode.map(|m| {
+            match firmware.and_then(|firmware| VehicleMode::from_custom_mode(firmware, m)) {
+                Some(mode) => format!("{:?}", mode),
+                None => format!("{}", m),
+            }
+        });
+        let (params, total) = v.parameters.progress();
+        format!("mode: {}\narmed: {}\nsystem status: {:?}\nlocation: {:?}\nlocal: {:?}\n\
+                 attitude: {:?}\nheading: {:?} groundspeed: {:?} airspeed: {:?}\ngps: {:?}\n\
+                 battery: {:?}\nekf ok: {}\nhome: {:?}\nlast heartbeat: {:?}s\n\
+                 parameters: {}/{}",
+                mode.unwrap_or("?".into()),
+                v.armed,
+                v.system_status,
+                v.location_global_relative,
+                v.location_local,
+                v.attitude,
+                v.heading,
+                v.groundspeed,
+                v.airspeed,
+                v.gps,
+                v.battery,
+                v.ekf_ok,
+                v.home_location,
+                v.last_heartbeat(),
+                params,
+                total)
+    })
+}
+
+fn watch(handle: &VehicleHandle, name: &str, seconds: u64) {
+    // Message ids subscribe directly; names are matched against each message.
+    let id = name.parse::<u8>().ok();
+    let name = name.to_uppercase();
+    let subscription = handle.with(move |v| {
+        match id {
+            Some(id) => v.subscribe(&[id], 64),
+            None => v.subscribe(&[], 64),
+        }
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(seconds);
+    while Instant::now() < deadline {
+        match subscription.try_recv() {
+            Ok(msg) => {
+                if id.is_some() || msg.name() == name {
+                    println!("{:?}", msg);
+                }
+            }
+            Err(..) => thread::sleep(Duration::from_millis(10)),
+        }
+    }
+    if subscription.dropped() > 0 {
+        println!("({} messages dropped)", subscription.dropped());
+    }
+}
+
+fn float(s: &str) -> Result<f32, String> {
+    s.parse().map_err(|_| format!("not a number: {:?}", s))
+}
+
+fn run(handle: &VehicleHandle, args: &[&str]) -> Result<(), String> {
+    match args {
+        ["mode", name] => {
+            let mode = try!(VehicleMode::from_name(name).ok_or(format!("unknown mode {:?}", name)));
+            wait(handle.set_mode(mode))
+        }
+        ["arm"] => wait(handle.arm()),
+        ["disarm"] => wait(handle.disarm()),
+        ["takeoff", alt] => wait(handle.takeoff(try!(float(alt)))),
+        ["goto", x, y, z] => {
+            wait(handle.goto(LocationLocal {
+                x: try!(float(x)),
+                y: try!(float(y)),
+                z: try!(float(z)),
+            }))
+        }
+        ["param", "show"] | ["param", "show", _] => {
+            let pattern = args.get(2).map(|p| p.to_uppercase()).unwrap_or("".into());
+            let mut params = handle.with(|v| v.parameters.list());
+            params.sort_by(|a, b| a.0.cmp(&b.0));
+            for (name, value) in params {
+                if name.contains(&*pattern) {
+                    println!("{:<16} {}", name, value);
+                }
+            }
+            Ok(())
+        }
+        ["param", "set", name, value] => {
+            let value = try!(float(value));
+            wait_set(handle.set_parameter(name, value))
+        }
+        ["param", "fetch", name] => {
+            let name: String = name.to_uppercase();
+            let value = try!(wait(handle.with(move |v| v.parameters.fetch(&name))));
+            println!("{}", value);
+            Ok(())
+        }
+        ["wp", "list"] => {
+            let items = try!(wait(handle.with(|v| v.mission.fetch())));
+            for (i, item) in items.iter().enumerate() {
+                println!("{:>3} cmd {:<5} frame {} ({:.7}, {:.7}, {:.2}) params [{}, {}, {}, {}]",
+                         i,
+                         item.command,
+                         item.frame,
+                         item.x,
+                         item.y,
+                         item.z,
+                         item.param1,
+                         item.param2,
+                         item.param3,
+                         item.param4);
+            }
+            Ok(())
+        }
+        ["wp", "load", path] => {
+            let items = try!(mission::load(path).map_err(|e| e.to_string()));
+            println!("uploading {} items", items.len());
+            wait(handle.with(move |v| {
+                // Only rewrite what changed if the vehicle's mission is cached
+                // and has the same length.
+                if v.mission.items().map_or(false, |cached| cached.len() == items.len()) {
+                    v.mission.update(items)
+                } else {
+                    v.mission.upload(items)
+                }
+            }))
+        }
+        ["wp", "save", path] => {
+            let items = try!(wait(handle.with(|v| v.mission.fetch())));
+            try!(mission::save(path, &items).map_err(|e| e.to_string()));
+            println!("saved {} items", items.len());
+            Ok(())
+        }
+        ["status"] => {
+            println!("{}", status(handle));
+            Ok(())
+        }
+        ["stats"] => {
+            let before = handle.with(|v| v.link_stats());
+            thread::sleep(Duration::from_secs(1));
+            let after = handle.with(|v| v.link_stats());
+            print!("{}", after);
+            let rates = after.rates_since(&before);
+            println!("rx {:.1} frames/s, {:.0} B/s; tx {:.1} frames/s, {:.0} B/s",
+                     rates.received,
+                     rates.bytes_received,
+                     rates.sent,
+                     rates.bytes_sent);
+            for (id, rate) in rates.messages {
+                if rate > 0.0 {
+                    println!("  id {:<3} {:.1}/s", id, rate);
+                }
+            }
+            Ok(())
+        }
+        ["watch", name] => {
+            watch(handle, name, 5);
+            Ok(())
+        }
+        ["watch", name, seconds] => {
+            let seconds = try!(seconds.parse().map_err(|_| format!("not a number: {:?}", seconds)));
+            watch(handle, name, seconds);
+            Ok(())
+        }
+        ["help"] => {
+            println!("{}", HELP);
+            Ok(())
+        }
+        _ => Err("unknown command; try `help`".into()),
+    }
+}
+
+fn main() {
+    let address = env::args().nth(1).unwrap_or("127.0.0.1:5760".into());
+    let address = match address.parse::<SocketAddr>() {
+        Ok(address) => address,
+        Err(..) => {
+            println!("usage: cli [HOST:PORT]");
+            return;
+        }
+    };
+
+    println!("connecting to {}...", address);
+    let handle = VehicleHandle::spawn(connect(address));
+    // Blocks until the vehicle thread has seen a heartbeat.
+    handle.with(|_| ());
+    println!("connected. type `help` for commands.");
+
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+        let args: Vec<&str> = line.split_whitespace().collect();
+        match args.first() {
+            None => continue,
+            Some(&"quit") | Some(&"exit") => break,
+            _ => {}
+        }
+        match run(&handle, &args) {
+            Ok(()) => {}
+            Err(e) => println!("error: {}", e),
+        }
+    }
+}
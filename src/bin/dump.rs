@@ -0,0 +1,311 @@
+extern crate dronekit;
+extern crate time;
+
+// Prints decoded messages from a live link or a telemetry log.
+//
+//     dump SOURCE [--msg NAME[,NAME...]] [--sysid ID] [--rate HZ]
+//                 [--format text|json|csv] [--stats]
+//
+// Sources:
+//
+//     tcp:HOST:PORT      connect to a TCP server
+//     udpin:ADDR:PORT    listen for UDP
+//     serial:PATH        a serial device, whose baud rate is set beforehand
+//     PATH               a .tlog file
+//
+// `--rate` limits how often each message type from each sender is printed.
+// CSV output has one column per field, so it needs exactly one `--msg` name.
+// `--stats` prints link statistics to stderr every few seconds and at the end.
+
+use dronekit::connection::{MavFrame, read_frames_with_stats};
+use dronekit::mavlink::MavValue;
+use dronekit::stats::LinkStats;
+use dronekit::tlog::TlogReader;
+
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, Read};
+use std::net::{TcpStream, UdpSocket};
+use std::process;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+struct Options {
+    names: Option<HashSet<String>>,
+    system_id: Option<u8>,
+    min_interval: u64,
+    format: Format,
+    stats: bool,
+}
+
+// How often link statistics are printed for live sources.
+const STATS_INTERVAL_NS: u64 = 5_000_000_000;
+
+struct Printer {
+    options: Options,
+    stats: LinkStats,
+    stats_printed: u64,
+    last_printed: HashMap<(u8, u8, u8), u64>,
+    csv_header_printed: bool,
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// JSON has no non-finite numbers, so those become null.
+fn json_value(value: &MavValue) -> String {
+    match *value {
+        MavValue::UInt(x) => x.to_string(),
+        MavValue::Int(x) => x.to_string(),
+        MavValue::Float(x) if x.is_finite() => x.to_string(),
+        MavValue::Float(_) => "null".into(),
+        MavValue::Text(ref text) => json_string(text),
+        MavValue::Array(ref values) => {
+            let values: Vec<String> = values.iter().map(json_value).collect();
+            format!("[{}]", values.join(","))
+        }
+    }
+}
+
+fn csv_value(value: &MavValue) -> String {
+    let text = match *value {
+        MavValue::UInt(x) => return x.to_string(),
+        MavValue::Int(x) => return x.to_string(),
+        MavValue::Float(x) => return x.to_string(),
+        MavValue::Text(ref text) => text.clone(),
+        MavValue::Array(ref values) => {
+            let values: Vec<String> = values.iter().map(csv_value).collect();
+            format!("[{}]", values.join(","))
+        }
+    };
+    if text.contains(|c: char| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+impl Printer {
+    fn print_stats(&mut self, force: bool) {
+        let now = time::precise_time_ns();
+        if self.options.stats && (force || now - self.stats_printed >= STATS_INTERVAL_NS) {
+            eprint!("{}", self.stats);
+            self.stats_printed = now;
+        }
+    }
+
+    fn print(&mut self, timestamp: u64, frame: &MavFrame) {
+        let msg = match frame.parse() {
+            Some(msg) => msg,
+            None => return,
+        };
+        let header = frame.header;
+
+        if self.options.names.as_ref().map_or(false, |names| !names.contains(msg.name())) ||
+           self.options.system_id.map_or(false, |id| id != header.system_id) {
+            return;
+        }
+
+        if self.options.min_interval > 0 {
+            let key = (header.system_id, header.component_id, frame.message_id);
+            if let Some(&last) = self.last_printed.get(&key) {
+                if timestamp < last + self.options.min_interval {
+                    return;
+                }
+            }
+            self.last_printed.insert(key, timestamp);
+        }
+
+        let seconds = timestamp as f64 / 1e6;
+        match self.options.format {
+            Format::Text => {
+                println!("{:.3} [{}:{}] {:?}",
+                         seconds,
+                         header.system_id,
+                         header.component_id,
+                         msg);
+            }
+            Format::Json => {
+                let body: Vec<String> = msg.fields()
+                                           .iter()
+                                           .map(|&(name, ref value)| {
+                                               format!("\"{}\":{}", name, json_value(value))
+                                           })
+                                           .collect();
+                println!("{{\"time\":{:.6},\"sysid\":{},\"compid\":{},\"seq\":{},\"msg\":\"{}\",\
+                          \"fields\":{{{}}}}}",
+                         seconds,
+                         header.system_id,
+                         header.component_id,
+                         header.sequence,
+                         msg.name(),
+                         body.join(","));
+            }
+            Format::Csv => {
+                let fields = msg.fields();
+                // CSV output holds a single message type, so the header is
+                // printed once.
+                if !self.csv_header_printed {
+                    let names: Vec<&str> = fields.iter().map(|&(name, _)| name).collect();
+                    println!("time,sysid,compid,msg,{}", names.join(","));
+                    self.csv_header_printed = true;
+                }
+                let values: Vec<String> = fields.iter()
+                                                .map(|&(_, ref value)| csv_value(value))
+                                                .collect();
+                println!("{:.6},{},{},{},{}",
+                         seconds,
+                         header.system_id,
+                         header.component_id,
+                         msg.name(),
+                         values.join(","));
+            }
+        }
+    }
+}
+
+fn now_us() -> u64 {
+    let now = time::get_time();
+    now.sec as u64 * 1_000_000 + now.nsec as u64 / 1000
+}
+
+fn dump_stream<R: Read>(mut reader: R, printer: &mut Printer) {
+    let mut buf = vec![];
+    let mut chunk = [0; 4096];
+    loop {
+        match reader.read(&mut chunk) {
+            Ok(0) | Err(..) => break,
+            Ok(n) => {
+                buf.extend(&chunk[..n]);
+                printer.stats.bytes_received += n as u64;
+                let timestamp = now_us();
+                for frame in read_frames_with_stats(&mut buf, &mut printer.stats) {
+                    printer.print(timestamp, &frame);
+                }
+                printer.print_stats(false);
+            }
+        }
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("usage: dump SOURCE [--msg NAME[,NAME...]] [--sysid ID] [--rate HZ] \
+              [--format text|json|csv] [--stats]");
+    eprintln!("sources: tcp:HOST:PORT udpin:ADDR:PORT serial:PATH FILE.tlog");
+    process::exit(1);
+}
+
+fn fail(message: String) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+fn main() {
+    let mut source = None;
+    let mut options = Options {
+        names: None,
+        system_id: None,
+        min_interval: 0,
+        format: Format::Text,
+        stats: false,
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| usage());
+        match &*arg {
+            "--msg" => {
+                options.names = Some(value().split(',').map(|x| x.trim().to_uppercase()).collect());
+            }
+            "--sysid" => options.system_id = Some(value().parse().unwrap_or_else(|_| usage())),
+            "--rate" => {
+                let rate: f64 = value().parse().unwrap_or_else(|_| usage());
+                if !rate.is_finite() || rate <= 0.0 {
+                    fail(format!("--rate must be a positive number, not {}", rate));
+                }
+                options.min_interval = (1e6 / rate) as u64;
+            }
+            "--format" => {
+                options.format = match &*value() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    _ => usage(),
+                };
+            }
+            "--stats" => options.stats = true,
+            _ if source.is_none() && !arg.starts_with("--") => source = Some(arg.clone()),
+            _ => usage(),
+        }
+    }
+    let source = source.unwrap_or_else(|| usage());
+    if options.format == Format::Csv &&
+       options.names.as_ref().map_or(true, |names| names.len() != 1) {
+        fail("--format csv needs a single message type given with --msg".into());
+    }
+
+    let mut printer = Printer {
+        options: options,
+        stats: LinkStats::new(),
+        stats_printed: time::precise_time_ns(),
+        last_printed: HashMap::new(),
+        csv_header_printed: false,
+    };
+
+    let mut kv = source.splitn(2, ':');
+    match (kv.next(), kv.next()) {
+        (Some("tcp"), Some(address)) => {
+            let stream = TcpStream::connect(address).unwrap_or_else(|e| fail(e.to_string()));
+            dump_stream(stream, &mut printer);
+        }
+        (Some("serial"), Some(path)) => {
+            let file = OpenOptions::new()
+                           .read(true)
+                           .write(true)
+                           .open(path)
+                           .unwrap_or_else(|e| fail(e.to_string()));
+            dump_stream(file, &mut printer);
+        }
+        (Some("udpin"), Some(address)) => {
+            let socket = UdpSocket::bind(address).unwrap_or_else(|e| fail(e.to_string()));
+            let mut chunk = [0; 65536];
+            while let Ok((n, _)) = socket.recv_from(&mut chunk) {
+                printer.stats.bytes_received += n as u64;
+                let timestamp = now_us();
+                for frame in read_frames_with_stats(&mut chunk[..n].to_vec(), &mut printer.stats) {
+                    printer.print(timestamp, &frame);
+                }
+                printer.print_stats(false);
+            }
+        }
+        _ => {
+            let file = File::open(&source).unwrap_or_else(|e| fail(e.to_string()));
+            let mut reader = TlogReader::new(BufReader::new(file));
+            while let Some((timestamp, frame)) = reader.next() {
+                printer.print(timestamp, &frame);
+            }
+            printer.stats = reader.stats().snapshot();
+        }
+    }
+    printer.print_stats(true);
+}
@@ -14,7 +14,7 @@ use mio::{TryRead, TryWrite};
 use mio::tcp::TcpStream;
 use mio::util::Slab;
 use bytes::Buf;
-use std::{mem, str};
+use std::{env, mem, str};
 use std::io::Cursor;
 use std::net::SocketAddr;
 use std::sync::mpsc::channel;
@@ -42,10 +42,54 @@ pub fn extra_crc(msg: &MavMessage) -> u8 {
     ((crcval & 0xFF) ^ (crcval >> 8)) as u8
 }
 
+// An expression converting the field `expr` to a `MavValue`. Char arrays
+// become text up to the first NUL.
+fn field_value(mavtype: &MavType, expr: &str) -> String {
+    match *mavtype {
+        MavType::Array(ref t, _) => {
+            match **t {
+                MavType::Char => format!("MavValue::Text(text(&{}))", expr),
+                ref t => {
+                    format!("MavValue::Array({}.iter().map(|&x| {}).collect())",
+                            expr,
+                            field_value(t, "x"))
+                }
+            }
+        }
+        MavType::UInt64 => format!("MavValue::UInt({})", expr),
+        MavType::Int64 => format!("MavValue::Int({})", expr),
+        MavType::Double => format!("MavValue::Float({})", expr),
+        MavType::Float => format!("MavValue::Float({} as f64)", expr),
+        MavType::Int8 | MavType::Int16 | MavType::Int32 => format!("MavValue::Int({} as i64)", expr),
+        _ => format!("MavValue::UInt({} as u64)", expr),
+    }
+}
+
+// Generates the dialect from the definition files given as arguments,
+// `common.xml` by default. Messages from later files, such as the fence and
+// rally messages of `ardupilotmega.xml`, are merged in by id.
 pub fn main() {
-    let file = File::open("common.xml").unwrap();
-    let file = BufReader::new(file);
-    let profile = parse_profile(Box::new(file));
+    let mut paths: Vec<String> = env::args().skip(1).collect();
+    if paths.is_empty() {
+        paths.push("common.xml".into());
+    }
+
+    let mut profile = MavProfile {
+        includes: vec![],
+        messages: vec![],
+        enums: vec![],
+    };
+    for path in &paths {
+        let file = BufReader::new(File::open(path).unwrap());
+        let dialect = parse_profile(Box::new(file));
+        for message in dialect.messages {
+            if !profile.messages.iter().any(|x| x.id == message.id) {
+                profile.messages.push(message);
+            }
+        }
+        profile.enums.extend(dialect.enums);
+    }
+    profile.messages.sort_by(|a, b| a.id.cmp(&b.id));
 
     println!("#![allow(non_camel_case_types)]");
     println!("#![allow(non_snake_case)]");
@@ -61,6 +105,22 @@ pub fn main() {
     println!("}}");
     println!("");
 
+    println!("// The value of a message field, for printing and serializing messages");
+    println!("// without matching on each type.");
+    println!("#[derive(Clone, Debug, PartialEq)]");
+    println!("pub enum MavValue {{");
+    println!("    UInt(u64),");
+    println!("    Int(i64),");
+    println!("    Float(f64),");
+    println!("    Text(String),");
+    println!("    Array(Vec<MavValue>),");
+    println!("}}");
+    println!("");
+    println!("fn text(buf: &[u8]) -> String {{");
+    println!("    buf.iter().take_while(|&&x| x != 0).map(|&x| x as char).collect()");
+    println!("}}");
+    println!("");
+
     for item in &profile.messages {
         let mut f = item.fields.clone();
         f.sort_by(|a, b| a.mavtype.compare(&b.mavtype));
@@ -175,6 +235,24 @@ pub fn main() {
         println!("    }}");
         println!("}}");
         println!("");
+
+        println!("impl {}_DATA {{", item.name);
+        println!("    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {{");
+        println!("        vec![");
+        for field in &f {
+            let fname = if field.name == "type" {
+                "mavtype".into()
+            } else {
+                field.name.clone()
+            };
+            println!("            (\"{}\", {}),",
+                     field.name,
+                     field_value(&field.mavtype, &format!("self.{}", fname)));
+        }
+        println!("        ]");
+        println!("    }}");
+        println!("}}");
+        println!("");
     }
 
     println!("#[derive(Clone, Debug)]");
@@ -206,6 +284,14 @@ pub fn main() {
     println!("        }}");
     println!("    }}");
     println!("");
+    println!("    pub fn name(&self) -> &'static str {{");
+    println!("        match self {{");
+    for item in &profile.messages {
+        println!("            &DkMessage::{}(..) => \"{}\",", item.name, item.name);
+    }
+    println!("        }}");
+    println!("    }}");
+    println!("");
     println!("    pub fn extra_crc(id: u8) -> u8 {{");
     println!("        match id {{");
     for item in &profile.messages {
@@ -215,6 +301,28 @@ pub fn main() {
     println!("        }}");
     println!("    }}");
     println!("");
+    println!("    // The target system and component of a message, or `None` if it is");
+    println!("    // broadcast. A message with no target component addresses every");
+    println!("    // component of its target system.");
+    println!("    pub fn target(&self) -> Option<(u8, u8)> {{");
+    println!("        match self {{");
+    for item in &profile.messages {
+        let has_field = |name: &str| item.fields.iter().any(|f| f.name == name);
+        if has_field("target_system") {
+            let component = if has_field("target_component") {
+                "body.target_component"
+            } else {
+                "0"
+            };
+            println!("            &DkMessage::{}(ref body) => Some((body.target_system, {})),",
+                     item.name,
+                     component);
+        }
+    }
+    println!("            _ => None,");
+    println!("        }}");
+    println!("    }}");
+    println!("");
     println!("    pub fn serialize(&self) -> Vec<u8> {{");
     println!("        match self {{");
     for item in &profile.messages {
@@ -223,6 +331,15 @@ pub fn main() {
     }
     println!("        }}");
     println!("    }}");
+    println!("");
+    println!("    // Field names, as in the message definitions, and values in wire order.");
+    println!("    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {{");
+    println!("        match self {{");
+    for item in &profile.messages {
+        println!("            &DkMessage::{}(ref body) => body.fields(),", item.name);
+    }
+    println!("        }}");
+    println!("    }}");
     println!("}}");
     println!("");
 }
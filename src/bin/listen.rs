@@ -3,7 +3,7 @@ extern crate eventual;
 
 use dronekit::vehicle::{Vehicle, VehicleMode, LocationLocal};
 use dronekit::connect;
-use eventual::{Async, join};
+use eventual::Async;
 
 fn main() {
     let mut vehicle = Vehicle::new(connect("127.0.0.1:5760".parse().unwrap()));
@@ -11,10 +11,7 @@ fn main() {
 
     vehicle.parameters.complete().await().unwrap();
 
-    join((vehicle.parameters.set("FS_GCS_ENABLE", 0.0),
-          vehicle.parameters.set("FS_EKF_THRESH", 100.0)))
-        .await()
-        .unwrap();
+    vehicle.parameters.set("FS_EKF_THRESH", 100.0).await().unwrap();
     vehicle.update(true);
 
     println!("sleeping");
@@ -0,0 +1,491 @@
+extern crate dronekit;
+extern crate time;
+
+// Forwards MAVLink frames between endpoints, like MAVProxy's --out.
+//
+//     router ENDPOINT [ENDPOINT...] [--dedup-ms N]
+//
+// Endpoints:
+//
+//     tcp:HOST:PORT      connect to a TCP server
+//     tcpin:ADDR:PORT    accept TCP clients, each becoming an endpoint
+//     udp:HOST:PORT      send to a UDP address
+//     udpin:ADDR:PORT    listen for UDP, replying to every sender heard from
+//                        in the last 30 seconds
+//     serial:PATH        a serial device
+//
+// An endpoint may be followed by `?allow=ID,ID` or `?block=ID,ID` (joined
+// with `&`) to restrict the message ids sent to it. Serial endpoints also
+// take `?baud=N`, which sets the baud rate with stty; otherwise it must be set
+// beforehand.
+//
+// Each endpoint is written from its own thread. An endpoint that falls more
+// than a queue's worth of frames behind, such as a stalled TCP client, is
+// closed rather than holding up the others.
+//
+// Frames addressed to a system or component are only sent to endpoints where
+// that system or component has been heard from; others are sent to every
+// endpoint but the one they arrived on. Identical frames arriving within the
+// deduplication window (200ms by default, 0 to disable), e.g. over redundant
+// radios, are forwarded once.
+
+use dronekit::connection::{MavFrame, read_frames};
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::process::{self, Command};
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, sync_channel, Receiver, Sender, SyncSender, TrySendError};
+use std::thread;
+
+type EndpointId = usize;
+
+// Frames queued for an endpoint's writer before the endpoint is closed.
+const WRITE_QUEUE_LEN: usize = 256;
+
+// How long a `udpin` endpoint keeps replying to a sender it hasn't heard
+// from.
+const UDP_PEER_TIMEOUT_NS: u64 = 30_000_000_000;
+
+#[derive(Clone, Debug, Default)]
+struct Filter {
+    allow: Option<HashSet<u8>>,
+    block: HashSet<u8>,
+}
+
+impl Filter {
+    fn passes(&self, message_id: u8) -> bool {
+        self.allow.as_ref().map_or(true, |allow| allow.contains(&message_id)) &&
+        !self.block.contains(&message_id)
+    }
+}
+
+enum Spec {
+    Tcp(String),
+    TcpIn(String),
+    Udp(String),
+    UdpIn(String),
+    Serial(String, Option<u32>),
+}
+
+fn parse_ids(list: &str) -> Result<HashSet<u8>, String> {
+    list.split(',')
+        .map(|id| id.trim().parse::<u8>().map_err(|_| format!("bad message id {:?}", id)))
+        .collect()
+}
+
+fn parse_endpoint(arg: &str) -> Result<(Spec, Filter), String> {
+    let mut parts = arg.splitn(2, '?');
+    let address = parts.next().unwrap();
+
+    let mut filter = Filter::default();
+    let mut baud = None;
+    if let Some(query) = parts.next() {
+        for option in query.split('&') {
+            let mut kv = option.splitn(2, '=');
+            match (kv.next(), kv.next()) {
+                (Some("allow"), Some(ids)) => filter.allow = Some(try!(parse_ids(ids))),
+                (Some("block"), Some(ids)) => filter.block = try!(parse_ids(ids)),
+                (Some("baud"), Some(rate)) if address.starts_with("serial:") => {
+                    let rate = try!(rate.parse().map_err(|_| format!("bad baud rate {:?}", rate)));
+                    baud = Some(rate);
+                }
+                _ => return Err(format!("bad endpoint option {:?}", option)),
+            }
+        }
+    }
+
+    let mut kv = address.splitn(2, ':');
+    let spec = match (kv.next(), kv.next()) {
+        (Some("tcp"), Some(rest)) => Spec::Tcp(rest.into()),
+        (Some("tcpin"), Some(rest)) => Spec::TcpIn(rest.into()),
+        (Some("udp"), Some(rest)) => Spec::Udp(rest.into()),
+        (Some("udpin"), Some(rest)) => Spec::UdpIn(rest.into()),
+        (Some("serial"), Some(rest)) => Spec::Serial(rest.into(), baud),
+        _ => return Err(format!("bad endpoint {:?}", address)),
+    };
+    Ok((spec, filter))
+}
+
+enum Event {
+    Opened(EndpointId, Box<Write + Send>, Filter),
+    Frame(EndpointId, MavFrame),
+    Closed(EndpointId),
+}
+
+// The addresses a UDP endpoint sends to: a `udp` endpoint's fixed peer, or
+// the senders a `udpin` endpoint has heard from, with when they were last
+// heard.
+struct UdpPeers {
+    fixed: Option<SocketAddr>,
+    heard: HashMap<SocketAddr, u64>,
+}
+
+impl UdpPeers {
+    fn current(&mut self) -> Vec<SocketAddr> {
+        let now = time::precise_time_ns();
+        self.heard.retain(|_, &mut last| now - last < UDP_PEER_TIMEOUT_NS);
+        self.fixed.into_iter().chain(self.heard.keys().cloned()).collect()
+    }
+}
+
+struct UdpWriter {
+    socket: UdpSocket,
+    peers: Arc<Mutex<UdpPeers>>,
+}
+
+impl Write for UdpWriter {
+    fn write(&mut self, buf: &[u8]) -> ::std::io::Result<usize> {
+        let peers = self.peers.lock().unwrap().current();
+        for peer in peers {
+            try!(self.socket.send_to(buf, peer));
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> ::std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// Shuts a TCP connection down when the endpoint is closed, so that its
+// reader stops too.
+struct TcpWriter(TcpStream);
+
+impl Write for TcpWriter {
+    fn write(&mut self, buf: &[u8]) -> ::std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> ::std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl Drop for TcpWriter {
+    fn drop(&mut self) {
+        let _ = self.0.shutdown(Shutdown::Both);
+    }
+}
+
+// Reads frames from a stream until it closes.
+fn read_stream<R: Read>(id: EndpointId, mut reader: R, events: Sender<Event>) {
+    let mut buf = vec![];
+    let mut chunk = [0; 4096];
+    loop {
+        match reader.read(&mut chunk) {
+            Ok(0) | Err(..) => break,
+            Ok(n) => {
+                buf.extend(&chunk[..n]);
+                for frame in read_frames(&mut buf) {
+                    if events.send(Event::Frame(id, frame)).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+    let _ = events.send(Event::Closed(id));
+}
+
+fn read_udp(id: EndpointId, socket: UdpSocket, peers: Arc<Mutex<UdpPeers>>, events: Sender<Event>) {
+    let mut chunk = [0; 65536];
+    loop {
+        match socket.recv_from(&mut chunk) {
+            Ok((n, from)) => {
+                {
+                    let mut peers = peers.lock().unwrap();
+                    if peers.fixed.is_none() {
+                        peers.heard.insert(from, time::precise_time_ns());
+                    }
+                }
+                let mut buf = chunk[..n].to_vec();
+                for frame in read_frames(&mut buf) {
+                    if events.send(Event::Frame(id, frame)).is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(e) => {
+                println!("udp endpoint {} failed: {:?}", id, e);
+                break;
+            }
+        }
+    }
+    let _ = events.send(Event::Closed(id));
+}
+
+fn open_udp(id: EndpointId,
+            socket: UdpSocket,
+            peers: UdpPeers,
+            filter: Filter,
+            events: &Sender<Event>) {
+    let peers = Arc::new(Mutex::new(peers));
+    let writer = UdpWriter {
+        socket: socket.try_clone().unwrap(),
+        peers: peers.clone(),
+    };
+    events.send(Event::Opened(id, Box::new(writer), filter)).unwrap();
+    let events = events.clone();
+    thread::spawn(move || read_udp(id, socket, peers, events));
+}
+
+fn open(id: EndpointId,
+        spec: Spec,
+        filter: Filter,
+        next_id: Arc<Mutex<EndpointId>>,
+        events: &Sender<Event>)
+        -> Result<(), String> {
+    match spec {
+        Spec::Tcp(address) => {
+            let stream = try!(TcpStream::connect(&*address).map_err(|e| e.to_string()));
+            let reader = stream.try_clone().unwrap();
+            events.send(Event::Opened(id, Box::new(TcpWriter(stream)), filter)).unwrap();
+            let events = events.clone();
+            thread::spawn(move || read_stream(id, reader, events));
+        }
+        Spec::TcpIn(address) => {
+            let listener = try!(TcpListener::bind(&*address).map_err(|e| e.to_string()));
+            let events = events.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let stream = match stream {
+                        Ok(stream) => stream,
+                        Err(..) => continue,
+                    };
+                    let id = {
+                        let mut next_id = next_id.lock().unwrap();
+                        *next_id += 1;
+                        *next_id
+                    };
+                    println!("endpoint {}: tcp client {:?}", id, stream.peer_addr());
+                    let reader = stream.try_clone().unwrap();
+                    events.send(Event::Opened(id, Box::new(TcpWriter(stream)), filter.clone()))
+                          .unwrap();
+                    let events = events.clone();
+                    thread::spawn(move || read_stream(id, reader, events));
+                }
+            });
+        }
+        Spec::Udp(address) => {
+            let peer = try!(address.to_socket_addrs()
+                                   .ok()
+                                   .and_then(|mut addresses| addresses.next())
+                                   .ok_or_else(|| format!("bad address {:?}", address)));
+            let bind = if peer.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+            let socket = try!(UdpSocket::bind(bind).map_err(|e| e.to_string()));
+            let peers = UdpPeers {
+                fixed: Some(peer),
+                heard: HashMap::new(),
+            };
+            open_udp(id, socket, peers, filter, events);
+        }
+        Spec::UdpIn(address) => {
+            let socket = try!(UdpSocket::bind(&*address).map_err(|e| e.to_string()));
+            let peers = UdpPeers {
+                fixed: None,
+                heard: HashMap::new(),
+            };
+            open_udp(id, socket, peers, filter, events);
+        }
+        Spec::Serial(path, baud) => {
+            if let Some(baud) = baud {
+                let status = try!(Command::new("stty")
+                                      .arg("-F")
+                                      .arg(&path)
+                                      .arg(baud.to_string())
+                                      .arg("raw")
+                                      .arg("-echo")
+                                      .status()
+                                      .map_err(|e| format!("running stty: {}", e)));
+                if !status.success() {
+                    return Err(format!("stty failed to set {} baud", baud));
+                }
+            }
+            let file: File = try!(OpenOptions::new()
+                                      .read(true)
+                                      .write(true)
+                                      .open(&path)
+                                      .map_err(|e| e.to_string()));
+            let reader = file.try_clone().unwrap();
+            events.send(Event::Opened(id, Box::new(file), filter)).unwrap();
+            let events = events.clone();
+            thread::spawn(move || read_stream(id, reader, events));
+        }
+    }
+    Ok(())
+}
+
+// Writes queued frames until the queue is dropped or a write fails.
+fn write_endpoint(id: EndpointId,
+                  mut writer: Box<Write + Send>,
+                  frames: Receiver<Vec<u8>>,
+                  events: Sender<Event>) {
+    for bytes in frames.iter() {
+        if writer.write_all(&bytes).is_err() {
+            println!("endpoint {}: write failed", id);
+            let _ = events.send(Event::Closed(id));
+            return;
+        }
+    }
+}
+
+struct Endpoint {
+    queue: SyncSender<Vec<u8>>,
+    filter: Filter,
+    // Systems and (system, component) pairs heard from on this endpoint.
+    systems: HashSet<u8>,
+    components: HashSet<(u8, u8)>,
+}
+
+struct Router {
+    endpoints: HashMap<EndpointId, Endpoint>,
+    dedup_ns: u64,
+    recent: VecDeque<(u64, Vec<u8>)>,
+    recent_set: HashSet<Vec<u8>>,
+}
+
+impl Router {
+    // Returns whether an identical frame was seen within the window.
+    fn is_duplicate(&mut self, frame: &MavFrame) -> bool {
+        if self.dedup_ns == 0 {
+            return false;
+        }
+        let now = time::precise_time_ns();
+        while let Some(&(t, _)) = self.recent.front() {
+            if now - t < self.dedup_ns {
+                break;
+            }
+            let (_, bytes) = self.recent.pop_front().unwrap();
+            self.recent_set.remove(&bytes);
+        }
+        if self.recent_set.contains(&frame.bytes) {
+            return true;
+        }
+        self.recent.push_back((now, frame.bytes.clone()));
+        self.recent_set.insert(frame.bytes.clone());
+        false
+    }
+
+    fn route(&mut self, source: EndpointId, frame: MavFrame) {
+        // Frames may still arrive from an endpoint that has been closed.
+        match self.endpoints.get_mut(&source) {
+            Some(endpoint) => {
+                endpoint.systems.insert(frame.header.system_id);
+                endpoint.components.insert((frame.header.system_id, frame.header.component_id));
+            }
+            None => return,
+        }
+
+        if self.is_duplicate(&frame) {
+            return;
+        }
+
+        let target = frame.parse().and_then(|msg| msg.target());
+
+        let mut overflowed = vec![];
+        for (&id, endpoint) in self.endpoints.iter_mut() {
+            if id == source || !endpoint.filter.passes(frame.message_id) {
+                continue;
+            }
+            let wanted = match target {
+                None | Some((0, _)) => true,
+                Some((system, 0)) => endpoint.systems.contains(&system),
+                Some((system, component)) => endpoint.components.contains(&(system, component)),
+            };
+            if !wanted {
+                continue;
+            }
+            // A disconnected queue means the writer failed; its `Closed`
+            // event is on the way.
+            if let Err(TrySendError::Full(..)) = endpoint.queue.try_send(frame.bytes.clone()) {
+                overflowed.push(id);
+            }
+        }
+        for id in overflowed {
+            println!("endpoint {}: too far behind, closing", id);
+            self.endpoints.remove(&id);
+        }
+    }
+}
+
+fn usage() -> ! {
+    println!("usage: router ENDPOINT [ENDPOINT...] [--dedup-ms N]");
+    println!("endpoints: tcp:HOST:PORT tcpin:ADDR:PORT udp:HOST:PORT udpin:ADDR:PORT \
+              serial:PATH");
+    println!("options: ENDPOINT?allow=ID,ID&block=ID,ID, serial:PATH?baud=N");
+    process::exit(1);
+}
+
+fn main() {
+    let mut specs = vec![];
+    let mut dedup_ms = 200;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--dedup-ms" {
+            dedup_ms = match args.next().and_then(|x| x.parse().ok()) {
+                Some(ms) => ms,
+                None => usage(),
+            };
+        } else {
+            match parse_endpoint(&arg) {
+                Ok(spec) => specs.push(spec),
+                Err(e) => {
+                    println!("{}", e);
+                    usage();
+                }
+            }
+        }
+    }
+    if specs.is_empty() {
+        usage();
+    }
+
+    let (tx, rx) = channel();
+    let next_id = Arc::new(Mutex::new(0));
+    for (spec, filter) in specs {
+        let id = {
+            let mut next_id = next_id.lock().unwrap();
+            *next_id += 1;
+            *next_id
+        };
+        if let Err(e) = open(id, spec, filter, next_id.clone(), &tx) {
+            println!("endpoint {}: {}", id, e);
+            process::exit(1);
+        }
+    }
+
+    let mut router = Router {
+        endpoints: HashMap::new(),
+        dedup_ns: dedup_ms * 1_000_000,
+        recent: VecDeque::new(),
+        recent_set: HashSet::new(),
+    };
+
+    for event in rx.iter() {
+        match event {
+            Event::Opened(id, writer, filter) => {
+                let (queue, frames) = sync_channel(WRITE_QUEUE_LEN);
+                let events = tx.clone();
+                thread::spawn(move || write_endpoint(id, writer, frames, events));
+                router.endpoints.insert(id,
+                                        Endpoint {
+                                            queue: queue,
+                                            filter: filter,
+                                            systems: HashSet::new(),
+                                            components: HashSet::new(),
+                                        });
+            }
+            Event::Frame(id, frame) => router.route(id, frame),
+            Event::Closed(id) => {
+                if router.endpoints.remove(&id).is_some() {
+                    println!("endpoint {}: closed", id);
+                }
+            }
+        }
+    }
+}
@@ -8,14 +8,59 @@ use byteorder::{LittleEndian, ReadBytesExt};
 
 use mio::{TryRead, TryWrite};
 use mio::tcp::TcpStream;
+use std::cmp;
 use std::io::Cursor;
 use std::collections::VecDeque;
-use std::sync::mpsc::{Sender, Receiver, RecvError, TryRecvError};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, sync_channel, Sender, Receiver, RecvError, RecvTimeoutError, TryRecvError,
+                      TrySendError};
+use std::time::Duration;
 use eventual::Complete;
 
+use stats::LinkStats;
+use time;
+
 pub const CLIENT: mio::Token = mio::Token(0);
 
-pub type UpdaterList = Vec<Box<FnMut(DkMessage) -> bool>>;
+// Watchers, each limited to messages from one system id if given.
+pub type UpdaterList = Vec<(Option<u8>, Box<FnMut(MavHeader, DkMessage) -> bool>)>;
+
+// MAV_COMPONENT ids commonly targeted by commands.
+pub const MAV_COMP_ID_ALL: u8 = 0;
+pub const MAV_COMP_ID_AUTOPILOT1: u8 = 1;
+pub const MAV_COMP_ID_CAMERA: u8 = 100;
+pub const MAV_COMP_ID_GIMBAL: u8 = 154;
+pub const MAV_COMP_ID_ONBOARD_COMPUTER: u8 = 191;
+
+// MAV_TYPE values for the heartbeat we send.
+pub const MAV_TYPE_GCS: u8 = 6;
+pub const MAV_TYPE_ONBOARD_CONTROLLER: u8 = 18;
+
+// How often the event loop sends our heartbeat and checks for lost links.
+pub const HEARTBEAT_INTERVAL_MS: u64 = 1000;
+
+// How long a system may go without a heartbeat before its link is lost, by
+// default.
+pub const LINK_TIMEOUT_MS: u64 = 3000;
+
+const MAV_AUTOPILOT_INVALID: u8 = 8;
+
+// Changes in whether a system is heartbeating.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LinkEvent {
+    Lost(u8),
+    Restored(u8),
+}
+
+// The sender of a received message.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MavHeader {
+    pub system_id: u8,
+    pub component_id: u8,
+    pub sequence: u8,
+}
 
 #[derive(Debug)]
 struct MavPacket {
@@ -80,6 +125,86 @@ impl MavPacket {
     }
 }
 
+// A complete MAVLink frame with a valid checksum, as read off the wire.
+#[derive(Clone, Debug)]
+pub struct MavFrame {
+    pub header: MavHeader,
+    pub message_id: u8,
+    // The whole frame, including the start byte and checksum.
+    pub bytes: Vec<u8>,
+}
+
+impl MavFrame {
+    pub fn payload(&self) -> &[u8] {
+        &self.bytes[6..self.bytes.len() - 2]
+    }
+
+    pub fn parse(&self) -> Option<DkMessage> {
+        DkMessage::parse(self.message_id, self.payload())
+    }
+}
+
+// Removes and returns the complete frames at the front of `buf`, leaving any
+// partial frame in place. Bytes outside frames and frames with a bad checksum
+// are skipped one byte at a time, so a start byte in line noise can't swallow
+// the frames after it. Frames with ids this dialect doesn't define can't be
+// checked, since their CRC_EXTRA is unknown; they are returned unverified so
+// that they can still be forwarded.
+pub fn read_frames(buf: &mut Vec<u8>) -> Vec<MavFrame> {
+    read_frames_with_stats(buf, &mut LinkStats::new())
+}
+
+// Like `read_frames`, also counting frames and errors in `stats`.
+pub fn read_frames_with_stats(buf: &mut Vec<u8>, stats: &mut LinkStats) -> Vec<MavFrame> {
+    let mut frames = vec![];
+    let mut start: usize = 0;
+    loop {
+        match buf[start..].iter().position(|&x| x == 0xfe) {
+            Some(i) => {
+                if start + i + 8 > buf.len() {
+                    break;
+                }
+
+                let len = buf[start + i + 1] as usize;
+
+                if start + i + 8 + len > buf.len() {
+                    break;
+                }
+
+                let pktbuf = &buf[(start + i)..(start + i + 8 + len)];
+                let packet = MavPacket::new(pktbuf);
+                if DkMessage::extra_crc(packet.message_id) == 0 {
+                    stats.unknown_ids += 1;
+                } else if !packet.check_crc() {
+                    stats.crc_errors += 1;
+                    start += i + 1;
+                    continue;
+                }
+
+                let header = MavHeader {
+                    system_id: packet.system_id,
+                    component_id: packet.component_id,
+                    sequence: packet.seq,
+                };
+                stats.record_frame(&header, packet.message_id);
+                frames.push(MavFrame {
+                    header: header,
+                    message_id: packet.message_id,
+                    bytes: pktbuf.to_vec(),
+                });
+
+                start += i + 8 + len;
+            }
+            None => {
+                start = buf.len();
+                break;
+            }
+        }
+    }
+    *buf = buf.split_off(start);
+    frames
+}
+
 pub fn parse_mavlink_string(buf: &[u8]) -> String {
     buf.iter()
        .take_while(|a| **a != 0)
@@ -92,22 +217,43 @@ pub struct DkHandler {
     pub buf: Vec<u8>,
     pub vehicle_tx: Sender<DkHandlerRx>,
     pub watchers: UpdaterList,
+    // Messages from these system ids go to their own connection instead of
+    // `vehicle_tx`.
+    pub routes: HashMap<u8, Sender<DkHandlerRx>>,
+    pub systems: Arc<Mutex<BTreeSet<u8>>>,
+    pub stats: Arc<Mutex<LinkStats>>,
+    pub corks: usize,
+    // Our heartbeat, sent every `HEARTBEAT_INTERVAL_MS`.
+    pub heartbeat: Option<(Outbox, DkMessage)>,
+    // Monotonic time of the last heartbeat from each autopilot, and the
+    // systems whose link is currently lost.
+    pub heartbeats: HashMap<u8, u64>,
+    pub lost: BTreeSet<u8>,
+    pub link_timeout_ns: u64,
 }
 
 pub enum DkHandlerRx {
     RxCork,
-    RxMessage(DkMessage),
+    RxMessage(MavHeader, DkMessage),
+    RxLink(LinkEvent),
 }
 
 pub enum DkHandlerMessage {
     TxMessage(Vec<u8>),
-    TxWatcher(Box<FnMut(DkMessage) -> bool + Send>),
-    TxCork,
+    TxWatcher(Option<u8>, Box<FnMut(MavHeader, DkMessage) -> bool + Send>),
+    TxRoute(u8, Sender<DkHandlerRx>),
+    TxCork(Option<u8>),
     TxUncork,
+    TxHeartbeat(Option<(Outbox, DkMessage)>),
+    TxLinkTimeout(u64),
 }
 
 impl DkHandler {
-    fn dispatch(&mut self, pkt: DkMessage) {
+    fn sender_for(&self, system_id: Option<u8>) -> &Sender<DkHandlerRx> {
+        system_id.and_then(|id| self.routes.get(&id)).unwrap_or(&self.vehicle_tx)
+    }
+
+    fn dispatch(&mut self, header: MavHeader, pkt: DkMessage) {
         // match pkt.clone() {
         //     DkMessage::SYS_STATUS(..) |
         //     DkMessage::PARAM_VALUE(..) |
@@ -135,15 +281,59 @@ impl DkHandler {
         //     }
         // }
 
+        self.systems.lock().unwrap().insert(header.system_id);
+
+        if let DkMessage::HEARTBEAT(ref data) = pkt {
+            if data.autopilot != MAV_AUTOPILOT_INVALID {
+                self.heartbeats.insert(header.system_id, time::precise_time_ns());
+                if self.lost.remove(&header.system_id) {
+                    let event = DkHandlerRx::RxLink(LinkEvent::Restored(header.system_id));
+                    let _ = self.sender_for(Some(header.system_id)).send(event);
+                }
+            }
+        }
+
         let pkt2 = pkt.clone();
-        self.vehicle_tx.send(DkHandlerRx::RxMessage(pkt)).unwrap();
+        let routed = self.routes.contains_key(&header.system_id);
+        if routed {
+            let sent = self.routes[&header.system_id]
+                           .send(DkHandlerRx::RxMessage(header, pkt))
+                           .is_ok();
+            if !sent {
+                self.routes.remove(&header.system_id);
+            }
+        } else {
+            // The parent connection may be gone while routed ones remain, in
+            // which case messages from unrouted systems are dropped.
+            let _ = self.vehicle_tx.send(DkHandlerRx::RxMessage(header, pkt));
+        }
 
         let ups = self.watchers.split_off(0);
-        for mut x in ups.into_iter() {
-            if !x(pkt2.clone()) {
-                self.watchers.push(x);
+        for (system_id, mut x) in ups.into_iter() {
+            if system_id.map_or(false, |id| id != header.system_id) || !x(header, pkt2.clone()) {
+                self.watchers.push((system_id, x));
+            }
+        }
+    }
+
+    fn write(&mut self, msg: Vec<u8>) {
+        self.stats.lock().unwrap().record_sent(msg.len());
+        self.socket.try_write_buf(&mut Cursor::new(msg)).unwrap();
+    }
+
+    // Raises `LinkEvent::Lost` for systems that have stopped heartbeating.
+    fn supervise(&mut self) {
+        let now = time::precise_time_ns();
+        let mut lost = vec![];
+        for (&system_id, &last) in &self.heartbeats {
+            if now - last > self.link_timeout_ns && !self.lost.contains(&system_id) {
+                lost.push(system_id);
             }
         }
+        for system_id in lost {
+            self.lost.insert(system_id);
+            let _ = self.sender_for(Some(system_id)).send(DkHandlerRx::RxLink(LinkEvent::Lost(system_id)));
+        }
     }
 
     pub fn register(&mut self, event_loop: &mut mio::EventLoop<DkHandler>) {
@@ -176,49 +366,17 @@ impl mio::Handler for DkHandler {
                     Ok(Some(0)) => {
                         unimplemented!();
                     }
-                    Ok(Some(..)) => {
-                        let mut start: usize = 0;
-                        loop {
-                            match self.buf[start..].iter().position(|&x| x == 0xfe) {
-                                Some(i) => {
-                                    if start + i + 8 > self.buf.len() {
-                                        break;
-                                    }
-
-                                    let len = self.buf[start + i + 1] as usize;
-
-                                    if start + i + 8 + len > self.buf.len() {
-                                        break;
-                                    }
-
-                                    let packet;
-                                    {
-                                        let pktbuf = &self.buf[(start + i)..(start + i + 8 + len)];
-                                        packet = MavPacket::new(pktbuf);
-
-                                        // println!("ok {:?}", pktbuf);
-
-                                        if !packet.check_crc() {
-                                            // println!("failed CRC!");
-                                            start += i + 1;
-                                            continue;
-                                        }
-                                    }
-
-                                    // handle packet
-                                    if let Some(pkt) = packet.parse() {
-                                        self.dispatch(pkt);
-                                    }
-
-                                    // change this
-                                    start += i + 8 + len;
-                                }
-                                None => {
-                                    break;
-                                }
+                    Ok(Some(n)) => {
+                        let frames = {
+                            let mut stats = self.stats.lock().unwrap();
+                            stats.bytes_received += n as u64;
+                            read_frames_with_stats(&mut self.buf, &mut stats)
+                        };
+                        for frame in frames {
+                            if let Some(pkt) = frame.parse() {
+                                self.dispatch(frame.header, pkt);
                             }
                         }
-                        self.buf = self.buf.split_off(start);
 
                         // Re-register the socket with the event loop. The current
                         // state is used to determine whether we are currently reading
@@ -240,28 +398,127 @@ impl mio::Handler for DkHandler {
     fn notify(&mut self, event_loop: &mut mio::EventLoop<DkHandler>, message: DkHandlerMessage) {
         match message {
             DkHandlerMessage::TxMessage(msg) => {
-                self.socket.try_write_buf(&mut Cursor::new(msg)).unwrap();
+                self.write(msg);
             }
-            DkHandlerMessage::TxWatcher(func) => {
-                self.watchers.push(func);
+            DkHandlerMessage::TxWatcher(system_id, func) => {
+                self.watchers.push((system_id, func));
             }
-            DkHandlerMessage::TxCork => {
-                self.deregister(event_loop);
-                self.vehicle_tx.send(DkHandlerRx::RxCork).unwrap();
+            DkHandlerMessage::TxRoute(system_id, tx) => {
+                self.routes.insert(system_id, tx);
+            }
+            DkHandlerMessage::TxCork(system_id) => {
+                if self.corks == 0 {
+                    self.deregister(event_loop);
+                }
+                self.corks += 1;
+                let _ = self.sender_for(system_id).send(DkHandlerRx::RxCork);
             }
             DkHandlerMessage::TxUncork => {
-                self.register(event_loop);
+                self.corks -= 1;
+                if self.corks == 0 {
+                    self.register(event_loop);
+                }
+            }
+            DkHandlerMessage::TxHeartbeat(heartbeat) => {
+                self.heartbeat = heartbeat;
+                let msg = self.heartbeat.as_ref().map(|&(ref outbox, ref msg)| outbox.encode(msg.clone()));
+                if let Some(msg) = msg {
+                    self.write(msg);
+                }
+            }
+            DkHandlerMessage::TxLinkTimeout(timeout_ns) => {
+                self.link_timeout_ns = timeout_ns;
             }
         }
     }
+
+    fn timeout(&mut self, event_loop: &mut mio::EventLoop<DkHandler>, _: ()) {
+        let msg = self.heartbeat.as_ref().map(|&(ref outbox, ref msg)| outbox.encode(msg.clone()));
+        if let Some(msg) = msg {
+            self.write(msg);
+        }
+        self.supervise();
+        event_loop.timeout_ms((), HEARTBEAT_INTERVAL_MS).unwrap();
+    }
+}
+
+// A cloneable handle for sending messages, usable from watchers running on
+// the event loop thread. Shares its sequence counter with the connection.
+#[derive(Clone)]
+pub struct Outbox {
+    pub tx: mio::Sender<DkHandlerMessage>,
+    pub msg_id: Arc<AtomicUsize>,
+    pub target_system: u8,
+    pub target_component: u8,
+    pub source_system: u8,
+    pub source_component: u8,
+}
+
+impl Outbox {
+    pub fn send(&self, data: DkMessage) {
+        self.tx.send(DkHandlerMessage::TxMessage(self.encode(data))).unwrap();
+    }
+
+    // Encodes a packet from our source ids, using the next sequence number.
+    pub fn encode(&self, data: DkMessage) -> Vec<u8> {
+        let seq = self.msg_id.fetch_add(1, Ordering::SeqCst) as u8;
+        let mut pkt = MavPacket {
+            seq: seq,
+            system_id: self.source_system,
+            component_id: self.source_component,
+            message_id: data.message_id(),
+            data: data.serialize(),
+            checksum: 0,
+        };
+        pkt.update_crc();
+        pkt.encode()
+    }
+}
+
+// A bounded queue of messages matching a subscription. When the queue is
+// full, new messages are dropped and counted rather than blocking the event
+// loop. The subscription ends when this is dropped.
+pub struct Subscription {
+    rx: Receiver<DkMessage>,
+    dropped: Arc<AtomicUsize>,
+}
+
+impl Subscription {
+    pub fn recv(&self) -> Result<DkMessage, RecvError> {
+        self.rx.recv()
+    }
+
+    pub fn try_recv(&self) -> Result<DkMessage, TryRecvError> {
+        self.rx.try_recv()
+    }
+
+    // Number of messages discarded because the queue was full.
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::SeqCst)
+    }
 }
 
 pub struct VehicleConnection {
     pub tx: mio::Sender<DkHandlerMessage>,
     pub rx: Receiver<DkHandlerRx>,
-    pub msg_id: u8,
+    pub msg_id: Arc<AtomicUsize>,
     pub started: bool,
-    pub buffer: VecDeque<DkMessage>,
+    pub buffer: VecDeque<(MavHeader, DkMessage)>,
+    // The system this connection is routed for, or `None` for messages from
+    // every system without a route of its own.
+    pub system_id: Option<u8>,
+    // Ids placed in the target fields of outgoing commands. Zero broadcasts.
+    pub target_system: u8,
+    pub target_component: u8,
+    // Ids we send as, and the MAV_TYPE we report in heartbeats. These default
+    // to a ground station (255, 0, `MAV_TYPE_GCS`).
+    pub source_system: u8,
+    pub source_component: u8,
+    pub source_type: u8,
+    pub systems: Arc<Mutex<BTreeSet<u8>>>,
+    pub stats: Arc<Mutex<LinkStats>>,
+    // Link events received but not yet taken with `take_events`.
+    pub events: VecDeque<LinkEvent>,
 }
 
 impl VehicleConnection {
@@ -269,22 +526,100 @@ impl VehicleConnection {
     //     println!("tick. location: {:?}", self.vehicle.location_global);
     // }
 
+    // Returns a connection that receives only messages from `system_id`,
+    // which no longer reach this one, and targets commands at that system.
+    // Use one per `Vehicle` to control several vehicles over the same link.
+    // Watchers registered on this connection still see every system; see
+    // `watch`.
+    pub fn connect_system(&self, system_id: u8) -> VehicleConnection {
+        let (tx, rx) = channel();
+        self.tx.send(DkHandlerMessage::TxRoute(system_id, tx)).unwrap();
+        VehicleConnection {
+            tx: self.tx.clone(),
+            rx: rx,
+            msg_id: self.msg_id.clone(),
+            started: false,
+            buffer: VecDeque::new(),
+            system_id: Some(system_id),
+            target_system: system_id,
+            target_component: 0,
+            source_system: self.source_system,
+            source_component: self.source_component,
+            source_type: self.source_type,
+            systems: self.systems.clone(),
+            stats: self.stats.clone(),
+            events: VecDeque::new(),
+        }
+    }
+
+    // Sets the ids placed in outgoing packets, e.g. to identify as an
+    // onboard computer (`MAV_COMP_ID_ONBOARD_COMPUTER`) on the vehicle's own
+    // system rather than as a ground station.
+    pub fn set_source(&mut self, system_id: u8, component_id: u8, mav_type: u8) {
+        self.source_system = system_id;
+        self.source_component = component_id;
+        self.source_type = mav_type;
+    }
+
+    // Sets the component that commands are addressed to. Zero broadcasts to
+    // every component of the target system.
+    pub fn set_target_component(&mut self, component_id: u8) {
+        self.target_component = component_id;
+    }
+
+    // A snapshot of the statistics for the underlying link, shared by every
+    // connection on it.
+    pub fn stats(&self) -> LinkStats {
+        self.stats.lock().unwrap().snapshot()
+    }
+
+    // Sends `heartbeat` now and then every `HEARTBEAT_INTERVAL_MS` from the
+    // event loop, regardless of inbound traffic, replacing any heartbeat set
+    // before. The link is shared, so one heartbeat serves every connection
+    // on it.
+    pub fn start_heartbeat(&self, heartbeat: DkMessage) {
+        self.tx.send(DkHandlerMessage::TxHeartbeat(Some((self.outbox(), heartbeat)))).unwrap();
+    }
+
+    pub fn stop_heartbeat(&self) {
+        self.tx.send(DkHandlerMessage::TxHeartbeat(None)).unwrap();
+    }
+
+    // Sets how long an autopilot may go without heartbeating before
+    // `LinkEvent::Lost` is raised for it. Applies to the whole link.
+    pub fn set_link_timeout(&self, timeout: Duration) {
+        let timeout_ns = timeout.as_secs() * 1_000_000_000 + timeout.subsec_nanos() as u64;
+        self.tx.send(DkHandlerMessage::TxLinkTimeout(timeout_ns)).unwrap();
+    }
+
+    // Returns the link events received along with messages since the last
+    // call.
+    pub fn take_events(&mut self) -> Vec<LinkEvent> {
+        self.events.drain(..).collect()
+    }
+
+    // System ids heard on the link so far.
+    pub fn systems(&self) -> Vec<u8> {
+        self.systems.lock().unwrap().iter().cloned().collect()
+    }
+
     pub fn cork(&mut self) -> Vec<DkMessage> {
-        self.tx.send(DkHandlerMessage::TxCork).unwrap();
+        self.tx.send(DkHandlerMessage::TxCork(self.system_id)).unwrap();
 
         loop {
             match self.rx.recv() {
                 Ok(DkHandlerRx::RxCork) => {
                     break;
                 }
-                Ok(DkHandlerRx::RxMessage(msg)) => {
-                    self.buffer.push_back(msg);
+                Ok(DkHandlerRx::RxMessage(header, msg)) => {
+                    self.buffer.push_back((header, msg));
                 }
+                Ok(DkHandlerRx::RxLink(event)) => self.events.push_back(event),
                 _ => {}
             }
         }
 
-        self.buffer.clone().into_iter().collect()
+        self.buffer.iter().map(|&(_, ref msg)| msg.clone()).collect()
     }
 
     pub fn uncork(&mut self) {
@@ -292,14 +627,28 @@ impl VehicleConnection {
     }
 
     pub fn recv(&mut self) -> Result<DkMessage, RecvError> {
+        self.recv_with_header().map(|(_, msg)| msg)
+    }
+
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<DkMessage, RecvTimeoutError> {
+        self.recv_timeout_with_header(timeout).map(|(_, msg)| msg)
+    }
+
+    pub fn try_recv(&mut self) -> Result<DkMessage, TryRecvError> {
+        self.try_recv_with_header().map(|(_, msg)| msg)
+    }
+
+    // Like `recv`, but also returns the ids of the message's sender.
+    pub fn recv_with_header(&mut self) -> Result<(MavHeader, DkMessage), RecvError> {
         loop {
-            if let Some(msg) = self.buffer.pop_front() {
-                return Ok(msg);
+            if let Some(item) = self.buffer.pop_front() {
+                return Ok(item);
             } else {
                 match self.rx.recv() {
-                    Ok(DkHandlerRx::RxMessage(msg)) => {
-                        return Ok(msg);
+                    Ok(DkHandlerRx::RxMessage(header, msg)) => {
+                        return Ok((header, msg));
                     }
+                    Ok(DkHandlerRx::RxLink(event)) => self.events.push_back(event),
                     Ok(..) => continue,
                     Err(err) => {
                         return Err(err);
@@ -309,15 +658,37 @@ impl VehicleConnection {
         }
     }
 
-    pub fn try_recv(&mut self) -> Result<DkMessage, TryRecvError> {
+    pub fn recv_timeout_with_header(&mut self,
+                                    timeout: Duration)
+                                    -> Result<(MavHeader, DkMessage), RecvTimeoutError> {
+        loop {
+            if let Some(item) = self.buffer.pop_front() {
+                return Ok(item);
+            } else {
+                match self.rx.recv_timeout(timeout) {
+                    Ok(DkHandlerRx::RxMessage(header, msg)) => {
+                        return Ok((header, msg));
+                    }
+                    Ok(DkHandlerRx::RxLink(event)) => self.events.push_back(event),
+                    Ok(..) => continue,
+                    Err(err) => {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn try_recv_with_header(&mut self) -> Result<(MavHeader, DkMessage), TryRecvError> {
         loop {
-            if let Some(msg) = self.buffer.pop_front() {
-                return Ok(msg);
+            if let Some(item) = self.buffer.pop_front() {
+                return Ok(item);
             } else {
                 match self.rx.try_recv() {
-                    Ok(DkHandlerRx::RxMessage(msg)) => {
-                        return Ok(msg);
+                    Ok(DkHandlerRx::RxMessage(header, msg)) => {
+                        return Ok((header, msg));
                     }
+                    Ok(DkHandlerRx::RxLink(event)) => self.events.push_back(event),
                     Ok(..) => continue,
                     Err(err) => {
                         return Err(err);
@@ -327,25 +698,63 @@ impl VehicleConnection {
         }
     }
 
-    pub fn send(&mut self, data: DkMessage) {
-        let mut pkt = MavPacket {
-            seq: self.msg_id,
-            system_id: 255,
-            component_id: 0,
-            message_id: data.message_id(),
-            data: data.serialize(),
-            checksum: 0,
-        };
-        pkt.update_crc();
-        let out = pkt.encode();
-        // let outlen = out.len();
+    pub fn outbox(&self) -> Outbox {
+        Outbox {
+            tx: self.tx.clone(),
+            msg_id: self.msg_id.clone(),
+            target_system: self.target_system,
+            target_component: self.target_component,
+            source_system: self.source_system,
+            source_component: self.source_component,
+        }
+    }
+
+    pub fn send(&self, data: DkMessage) {
+        self.outbox().send(data);
+    }
+
+    // Registers a watcher without checking buffered messages. Suitable for
+    // request/response exchanges where the request is sent afterwards.
+    // Watchers on a connection from `connect_system` see only that system's
+    // messages; those on the parent connection see every system's, including
+    // systems routed elsewhere.
+    pub fn watch(&mut self, mut watch: Box<FnMut(DkMessage) -> bool + Send>) {
+        self.watch_with_header(Box::new(move |_, msg| watch(msg)));
+    }
+
+    // Like `watch`, but also passes the ids of each message's sender.
+    pub fn watch_with_header(&mut self, watch: Box<FnMut(MavHeader, DkMessage) -> bool + Send>) {
+        self.tx.send(DkHandlerMessage::TxWatcher(self.system_id, watch)).unwrap();
+    }
 
-        self.msg_id = self.msg_id.wrapping_add(1);
+    // Delivers copies of messages with the given ids (or all messages, if
+    // `ids` is empty) to a new queue holding up to `capacity` messages. A
+    // capacity of zero is treated as one, since a rendezvous queue would
+    // drop every message.
+    pub fn subscribe(&mut self, ids: &[u8], capacity: usize) -> Subscription {
+        let (tx, rx) = sync_channel(cmp::max(capacity, 1));
+        let dropped = Arc::new(AtomicUsize::new(0));
+
+        let ids = ids.to_vec();
+        let dropped_closure = dropped.clone();
+        self.watch(Box::new(move |msg| {
+            if !ids.is_empty() && !ids.contains(&msg.message_id()) {
+                return false;
+            }
+            match tx.try_send(msg) {
+                Ok(()) => false,
+                Err(TrySendError::Full(..)) => {
+                    dropped_closure.fetch_add(1, Ordering::SeqCst);
+                    false
+                }
+                Err(TrySendError::Disconnected(..)) => true,
+            }
+        }));
 
-        // println!(">>> {:?}", out);
-        // let mut cur = Cursor::new(out);
-        self.tx.send(DkHandlerMessage::TxMessage(out)).unwrap();
-        // (outlen, self.socket.try_write_buf(&mut cur))
+        Subscription {
+            rx: rx,
+            dropped: dropped,
+        }
     }
 
     pub fn complete(&mut self,
@@ -356,7 +765,7 @@ impl VehicleConnection {
         if !buffer.into_iter().any(|x| watch(x)) {
             let mut txlock = Some(tx);
             self.tx
-                .send(DkHandlerMessage::TxWatcher(Box::new(move |msg| {
+                .send(DkHandlerMessage::TxWatcher(self.system_id, Box::new(move |_, msg| {
                     if watch(msg) {
                         if let Some(tx) = txlock.take() {
                             tx.complete(());
@@ -374,3 +783,52 @@ impl VehicleConnection {
         self.uncork();
     }
 }
+
+#[test]
+fn test_read_frames_resync() {
+    // Encodes a frame, checksummed with the sender's CRC_EXTRA.
+    let frame = |message_id: u8, data: Vec<u8>, extra_crc: u8| {
+        let mut packet = MavPacket {
+            seq: 0,
+            system_id: 1,
+            component_id: 1,
+            message_id: message_id,
+            data: data,
+            checksum: 0,
+        };
+        let mut crc = crc16::State::<crc16::MCRF4XX>::new();
+        crc.update(&packet.encode_nocrc()[1..]);
+        crc.update(&[extra_crc]);
+        packet.checksum = crc.get();
+        packet.encode()
+    };
+
+    // A stray start byte declaring a long payload, followed by a heartbeat.
+    let heartbeat = frame(0, vec![0; 9], DkMessage::extra_crc(0));
+    let mut buf = vec![0xfe, 20, 0, 1, 1, 0];
+    buf.extend(&heartbeat);
+    buf.extend(&[0; 20]);
+
+    let mut stats = LinkStats::new();
+    let frames = read_frames_with_stats(&mut buf, &mut stats);
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].bytes, heartbeat);
+    assert_eq!(stats.unknown_ids, 0);
+    assert_eq!(stats.crc_errors, 1);
+
+    // Unknown ids can't be verified, so they are passed through and counted.
+    let unknown = frame(200, vec![1, 2, 3], 0x5a);
+    let mut buf = unknown.clone();
+    buf.extend(&heartbeat);
+    let frames = read_frames_with_stats(&mut buf, &mut stats);
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].bytes, unknown);
+    assert_eq!(stats.unknown_ids, 1);
+    assert_eq!(stats.crc_errors, 1);
+
+    // Known ids with a bad checksum are still dropped.
+    let mut buf = heartbeat.clone();
+    buf[7] ^= 0xff;
+    assert_eq!(read_frames_with_stats(&mut buf, &mut stats).len(), 0);
+    assert_eq!(stats.crc_errors, 2);
+}
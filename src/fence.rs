@@ -0,0 +1,311 @@
+// Geofences. This dialect's mission protocol has no mission type, so fences
+// are transferred with ArduPilot's FENCE_POINT protocol (see `points`), which
+// holds a return point and a single inclusion polygon; circular fences are
+// set with the FENCE_RADIUS parameter instead. `to_items` and `from_items`
+// convert to and from the fence mission items used by newer autopilots.
+
+use mavlink::*;
+
+use connection::Outbox;
+use mission::MissionItem;
+use points::PointProtocol;
+use vehicle::{Distance, LocationGlobal};
+
+const MAV_CMD_NAV_FENCE_RETURN_POINT: u16 = 5000;
+const MAV_CMD_NAV_FENCE_POLYGON_VERTEX_INCLUSION: u16 = 5001;
+const MAV_CMD_NAV_FENCE_POLYGON_VERTEX_EXCLUSION: u16 = 5002;
+const MAV_CMD_NAV_FENCE_CIRCLE_INCLUSION: u16 = 5003;
+const MAV_CMD_NAV_FENCE_CIRCLE_EXCLUSION: u16 = 5004;
+
+const MAV_FRAME_GLOBAL: u8 = 0;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FenceShape {
+    Polygon {
+        inclusion: bool,
+        vertices: Vec<LocationGlobal>,
+    },
+    Circle {
+        inclusion: bool,
+        center: LocationGlobal,
+        radius: f32,
+    },
+}
+
+impl FenceShape {
+    pub fn inclusion(&self) -> bool {
+        match *self {
+            FenceShape::Polygon { inclusion, .. } => inclusion,
+            FenceShape::Circle { inclusion, .. } => inclusion,
+        }
+    }
+
+    pub fn contains(&self, point: &LocationGlobal) -> bool {
+        match *self {
+            FenceShape::Polygon { ref vertices, .. } => polygon_contains(vertices, point),
+            FenceShape::Circle { ref center, radius, .. } => center.distance_to(point) <= radius,
+        }
+    }
+}
+
+// Even-odd ray casting in degree space, which is accurate enough for fences
+// that do not straddle the antimeridian.
+fn polygon_contains(vertices: &[LocationGlobal], point: &LocationGlobal) -> bool {
+    let (px, py) = (point.lon as f64, point.lat as f64);
+    let mut inside = false;
+    let mut j = vertices.len().wrapping_sub(1);
+    for i in 0..vertices.len() {
+        let (xi, yi) = (vertices[i].lon as f64, vertices[i].lat as f64);
+        let (xj, yj) = (vertices[j].lon as f64, vertices[j].lat as f64);
+        if (yi > py) != (yj > py) && px < (xj - xi) * (py - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fence {
+    pub shapes: Vec<FenceShape>,
+    pub return_point: Option<LocationGlobal>,
+}
+
+impl Fence {
+    pub fn new() -> Fence {
+        Fence {
+            shapes: vec![],
+            return_point: None,
+        }
+    }
+
+    // A point is inside the fence when it lies within every inclusion zone
+    // and outside every exclusion zone.
+    pub fn contains(&self, point: &LocationGlobal) -> bool {
+        self.shapes.iter().all(|shape| shape.contains(point) == shape.inclusion())
+    }
+
+    // Encodes the fence as mission items of the fence mission type.
+    pub fn to_items(&self) -> Vec<MissionItem> {
+        let mut items = vec![];
+        if let Some(ref point) = self.return_point {
+            items.push(fence_item(MAV_CMD_NAV_FENCE_RETURN_POINT, 0.0, point));
+        }
+        for shape in &self.shapes {
+            match *shape {
+                FenceShape::Polygon { inclusion, ref vertices } => {
+                    let command = if inclusion {
+                        MAV_CMD_NAV_FENCE_POLYGON_VERTEX_INCLUSION
+                    } else {
+                        MAV_CMD_NAV_FENCE_POLYGON_VERTEX_EXCLUSION
+                    };
+                    for vertex in vertices {
+                        items.push(fence_item(command, vertices.len() as f32, vertex));
+                    }
+                }
+                FenceShape::Circle { inclusion, ref center, radius } => {
+                    let command = if inclusion {
+                        MAV_CMD_NAV_FENCE_CIRCLE_INCLUSION
+                    } else {
+                        MAV_CMD_NAV_FENCE_CIRCLE_EXCLUSION
+                    };
+                    items.push(fence_item(command, radius, center));
+                }
+            }
+        }
+        items
+    }
+
+    // Decodes fence mission items. Returns `None` if an item is not a fence
+    // command or a polygon is truncated.
+    pub fn from_items(items: &[MissionItem]) -> Option<Fence> {
+        let mut fence = Fence::new();
+        let mut i = 0;
+        while i < items.len() {
+            let item = &items[i];
+            let point = item_location(item);
+            match item.command {
+                MAV_CMD_NAV_FENCE_RETURN_POINT => {
+                    fence.return_point = Some(point);
+                    i += 1;
+                }
+                MAV_CMD_NAV_FENCE_POLYGON_VERTEX_INCLUSION |
+                MAV_CMD_NAV_FENCE_POLYGON_VERTEX_EXCLUSION => {
+                    let count = item.param1 as usize;
+                    if count < 3 || i + count > items.len() {
+                        return None;
+                    }
+                    let vertices = &items[i..i + count];
+                    if vertices.iter().any(|x| x.command != item.command) {
+                        return None;
+                    }
+                    fence.shapes.push(FenceShape::Polygon {
+                        inclusion: item.command == MAV_CMD_NAV_FENCE_POLYGON_VERTEX_INCLUSION,
+                        vertices: vertices.iter().map(item_location).collect(),
+                    });
+                    i += count;
+                }
+                MAV_CMD_NAV_FENCE_CIRCLE_INCLUSION |
+                MAV_CMD_NAV_FENCE_CIRCLE_EXCLUSION => {
+                    fence.shapes.push(FenceShape::Circle {
+                        inclusion: item.command == MAV_CMD_NAV_FENCE_CIRCLE_INCLUSION,
+                        center: point,
+                        radius: item.param1,
+                    });
+                    i += 1;
+                }
+                _ => return None,
+            }
+        }
+        Some(fence)
+    }
+}
+
+impl Fence {
+    // The FENCE_POINT layout: the return point, the polygon's vertices, and
+    // the first vertex again to close it. Fails unless the fence is a return
+    // point and one inclusion polygon.
+    pub fn to_points(&self) -> Result<Vec<LocationGlobal>, String> {
+        let return_point = match self.return_point {
+            Some(ref point) => point.clone(),
+            None => return Err("fence points need a return point".into()),
+        };
+        match &self.shapes[..] {
+            &[FenceShape::Polygon { inclusion: true, ref vertices }] if vertices.len() >= 3 => {
+                let mut points = vec![return_point];
+                points.extend(vertices.iter().cloned());
+                points.push(vertices[0].clone());
+                Ok(points)
+            }
+            _ => Err("fence points hold a single inclusion polygon of at least 3 vertices".into()),
+        }
+    }
+
+    // Decodes the FENCE_POINT layout. Returns `None` if there are too few
+    // points for a polygon.
+    pub fn from_points(points: &[LocationGlobal]) -> Option<Fence> {
+        if points.len() < 4 {
+            return None;
+        }
+        let mut vertices = points[1..].to_vec();
+        if vertices.len() > 3 && same_point(&vertices[0], &vertices[vertices.len() - 1]) {
+            vertices.pop();
+        }
+        Some(Fence {
+            shapes: vec![FenceShape::Polygon {
+                             inclusion: true,
+                             vertices: vertices,
+                         }],
+            return_point: Some(points[0].clone()),
+        })
+    }
+}
+
+// FENCE_POINT carries degrees as floats, which hold about 7 significant
+// digits.
+fn same_point(a: &LocationGlobal, b: &LocationGlobal) -> bool {
+    (a.lat - b.lat).abs() <= 200 && (a.lon - b.lon).abs() <= 200
+}
+
+fn encode_point(outbox: &Outbox, index: u8, count: u8, point: &LocationGlobal) -> DkMessage {
+    DkMessage::FENCE_POINT(FENCE_POINT_DATA {
+        lat: (point.lat as f64 / 1e7) as f32,
+        lng: (point.lon as f64 / 1e7) as f32,
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+        idx: index,
+        count: count,
+    })
+}
+
+fn fetch_point(outbox: &Outbox, index: u8) -> DkMessage {
+    DkMessage::FENCE_FETCH_POINT(FENCE_FETCH_POINT_DATA {
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+        idx: index,
+    })
+}
+
+fn decode_point(msg: &DkMessage) -> Option<(u8, LocationGlobal)> {
+    match *msg {
+        DkMessage::FENCE_POINT(ref data) => {
+            Some((data.idx,
+                  LocationGlobal {
+                lat: (data.lat as f64 * 1e7).round() as i32,
+                lon: (data.lng as f64 * 1e7).round() as i32,
+                alt: 0,
+            }))
+        }
+        _ => None,
+    }
+}
+
+pub fn fence_protocol() -> PointProtocol<LocationGlobal> {
+    PointProtocol {
+        total_param: "FENCE_TOTAL",
+        encode: encode_point,
+        fetch: fetch_point,
+        decode: decode_point,
+        matches: same_point,
+    }
+}
+
+fn fence_item(command: u16, param1: f32, point: &LocationGlobal) -> MissionItem {
+    MissionItem {
+        command: command,
+        frame: MAV_FRAME_GLOBAL,
+        param1: param1,
+        param2: 0.0,
+        param3: 0.0,
+        param4: 0.0,
+        x: (point.lat as f64 / 1e7) as f32,
+        y: (point.lon as f64 / 1e7) as f32,
+        z: point.alt as f32 / 1000.0,
+        autocontinue: 0,
+    }
+}
+
+fn item_location(item: &MissionItem) -> LocationGlobal {
+    LocationGlobal {
+        lat: (item.x as f64 * 1e7).round() as i32,
+        lon: (item.y as f64 * 1e7).round() as i32,
+        alt: (item.z as f64 * 1000.0).round() as i32,
+    }
+}
+
+#[test]
+fn test_contains() {
+    let loc = |lat: f64, lon: f64| {
+        LocationGlobal {
+            lat: (lat * 1e7) as i32,
+            lon: (lon * 1e7) as i32,
+            alt: 0,
+        }
+    };
+
+    let mut fence = Fence::new();
+    fence.shapes.push(FenceShape::Polygon {
+        inclusion: true,
+        vertices: vec![loc(-35.0, 149.0), loc(-35.0, 149.1), loc(-35.1, 149.1), loc(-35.1, 149.0)],
+    });
+    fence.shapes.push(FenceShape::Circle {
+        inclusion: false,
+        center: loc(-35.05, 149.05),
+        radius: 100.0,
+    });
+
+    assert!(fence.contains(&loc(-35.02, 149.02)));
+    assert!(!fence.contains(&loc(-35.05, 149.05)));
+    assert!(!fence.contains(&loc(-35.2, 149.05)));
+
+    assert_eq!(Fence::from_items(&fence.to_items()).map(|x| x.shapes.len()),
+               Some(2));
+    assert!(fence.to_points().is_err());
+
+    fence.shapes.pop();
+    fence.return_point = Some(loc(-35.05, 149.05));
+    let points = fence.to_points().unwrap();
+    assert_eq!(points.len(), 6);
+    assert_eq!(points[5], points[1]);
+    assert_eq!(Fence::from_points(&points), Some(fence));
+}
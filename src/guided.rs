@@ -0,0 +1,234 @@
+// Setpoints for vehicles in GUIDED mode.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use mavlink::*;
+
+use connection::Outbox;
+use state::Velocity;
+use vehicle::{LocationGlobal, LocationGlobalRelative};
+
+// SET_POSITION_TARGET type_mask bits, set to ignore a field.
+const IGNORE_POSITION: u16 = 0b0000_0000_0111;
+const IGNORE_VELOCITY: u16 = 0b0000_0011_1000;
+const IGNORE_ACCELERATION: u16 = 0b0001_1100_0000;
+const IGNORE_YAW: u16 = 0b0100_0000_0000;
+const IGNORE_YAW_RATE: u16 = 0b1000_0000_0000;
+
+// Frames for velocity setpoints.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum VelocityFrame {
+    // North, east, down.
+    LocalNed,
+    // Forward, right, down relative to the vehicle's heading.
+    BodyNed,
+    // As `BodyNed`; ArduPilot treats the two alike for velocities.
+    BodyOffsetNed,
+}
+
+impl VelocityFrame {
+    // The MAV_FRAME value.
+    pub fn mav_frame(&self) -> u8 {
+        match *self {
+            VelocityFrame::LocalNed => 1,
+            VelocityFrame::BodyNed => 8,
+            VelocityFrame::BodyOffsetNed => 9,
+        }
+    }
+}
+
+// Builds a setpoint commanding `velocity` in meters per second and, if given,
+// a yaw rate in radians per second.
+pub fn velocity_target(outbox: &Outbox,
+                       frame: VelocityFrame,
+                       velocity: Velocity,
+                       yaw_rate: Option<f32>)
+                       -> DkMessage {
+    let mut type_mask = IGNORE_POSITION | IGNORE_ACCELERATION | IGNORE_YAW;
+    if yaw_rate.is_none() {
+        type_mask |= IGNORE_YAW_RATE;
+    }
+    DkMessage::SET_POSITION_TARGET_LOCAL_NED(SET_POSITION_TARGET_LOCAL_NED_DATA {
+        time_boot_ms: 0,
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+        coordinate_frame: frame.mav_frame(),
+        type_mask: type_mask,
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        vx: velocity.vx,
+        vy: velocity.vy,
+        vz: velocity.vz,
+        afx: 0.0,
+        afy: 0.0,
+        afz: 0.0,
+        yaw: 0.0,
+        yaw_rate: yaw_rate.unwrap_or(0.0),
+    })
+}
+
+// What the altitude of a global setpoint is measured from.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AltitudeFrame {
+    // Mean sea level.
+    Amsl,
+    // The home position.
+    Relative,
+    // The terrain below the vehicle, which requires terrain data.
+    Terrain,
+}
+
+impl AltitudeFrame {
+    // The MAV_FRAME value for integer coordinates.
+    pub fn mav_frame(&self) -> u8 {
+        match *self {
+            AltitudeFrame::Amsl => 5,
+            AltitudeFrame::Relative => 6,
+            AltitudeFrame::Terrain => 11,
+        }
+    }
+}
+
+// A position target for `Vehicle::goto_global`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GlobalTarget {
+    // Degrees * 1e7.
+    pub lat: i32,
+    pub lon: i32,
+    // Meters above `frame`.
+    pub alt: f32,
+    pub frame: AltitudeFrame,
+}
+
+impl From<LocationGlobal> for GlobalTarget {
+    fn from(location: LocationGlobal) -> GlobalTarget {
+        GlobalTarget {
+            lat: location.lat,
+            lon: location.lon,
+            alt: location.alt as f32 / 1000.0,
+            frame: AltitudeFrame::Amsl,
+        }
+    }
+}
+
+impl From<LocationGlobalRelative> for GlobalTarget {
+    fn from(location: LocationGlobalRelative) -> GlobalTarget {
+        GlobalTarget {
+            lat: location.lat,
+            lon: location.lon,
+            alt: location.alt as f32 / 1000.0,
+            frame: AltitudeFrame::Relative,
+        }
+    }
+}
+
+pub fn position_target(outbox: &Outbox, target: &GlobalTarget) -> DkMessage {
+    DkMessage::SET_POSITION_TARGET_GLOBAL_INT(SET_POSITION_TARGET_GLOBAL_INT_DATA {
+        time_boot_ms: 0,
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+        coordinate_frame: target.frame.mav_frame(),
+        type_mask: IGNORE_VELOCITY | IGNORE_ACCELERATION | IGNORE_YAW | IGNORE_YAW_RATE,
+        lat_int: target.lat,
+        lon_int: target.lon,
+        alt: target.alt,
+        vx: 0.0,
+        vy: 0.0,
+        vz: 0.0,
+        afx: 0.0,
+        afy: 0.0,
+        afz: 0.0,
+        yaw: 0.0,
+        yaw_rate: 0.0,
+    })
+}
+
+// The fastest setpoint rate accepted by `VelocityStream`.
+pub const MAX_SETPOINT_RATE_HZ: f32 = 50.0;
+
+// The time between setpoints sent at `rate_hz`, which must be above zero
+// and at most `MAX_SETPOINT_RATE_HZ`.
+fn setpoint_interval(rate_hz: f32) -> Result<Duration, String> {
+    if !(rate_hz > 0.0 && rate_hz <= MAX_SETPOINT_RATE_HZ) {
+        return Err(format!("setpoint rate {} Hz is not in (0, {}]", rate_hz, MAX_SETPOINT_RATE_HZ));
+    }
+    Ok(Duration::from_nanos((1e9 / rate_hz as f64) as u64))
+}
+
+// Resends a velocity setpoint at a fixed rate from its own thread, since
+// autopilots stop the vehicle if setpoints stop arriving (after 3 seconds on
+// ArduCopter). The stream stops when this is dropped.
+pub struct VelocityStream {
+    outbox: Outbox,
+    setpoint: Arc<Mutex<Option<DkMessage>>>,
+}
+
+impl VelocityStream {
+    // Fails without sending anything if `rate_hz` is not a positive rate up
+    // to `MAX_SETPOINT_RATE_HZ`.
+    pub fn start(outbox: Outbox,
+                 frame: VelocityFrame,
+                 velocity: Velocity,
+                 yaw_rate: Option<f32>,
+                 rate_hz: f32)
+                 -> Result<VelocityStream, String> {
+        let interval = try!(setpoint_interval(rate_hz));
+        let msg = velocity_target(&outbox, frame, velocity, yaw_rate);
+        outbox.send(msg.clone());
+        let setpoint = Arc::new(Mutex::new(Some(msg)));
+
+        let thread_outbox = outbox.clone();
+        let thread_setpoint = setpoint.clone();
+        thread::spawn(move || {
+            loop {
+                thread::sleep(interval);
+                match *thread_setpoint.lock().unwrap() {
+                    Some(ref msg) => thread_outbox.send(msg.clone()),
+                    None => break,
+                }
+            }
+        });
+
+        Ok(VelocityStream {
+            outbox: outbox,
+            setpoint: setpoint,
+        })
+    }
+
+    // Replaces the setpoint being streamed, sending it immediately.
+    pub fn set(&self, frame: VelocityFrame, velocity: Velocity, yaw_rate: Option<f32>) {
+        let msg = velocity_target(&self.outbox, frame, velocity, yaw_rate);
+        self.outbox.send(msg.clone());
+        *self.setpoint.lock().unwrap() = Some(msg);
+    }
+}
+
+impl Drop for VelocityStream {
+    fn drop(&mut self) {
+        *self.setpoint.lock().unwrap() = None;
+    }
+}
+
+#[test]
+fn test_global_target() {
+    let target = GlobalTarget::from(LocationGlobalRelative {
+        lat: -353632620,
+        lon: 1491652370,
+        alt: 30500,
+    });
+    assert_eq!(target.alt, 30.5);
+    assert_eq!(target.frame.mav_frame(), 6);
+}
+
+#[test]
+fn test_setpoint_interval() {
+    assert_eq!(setpoint_interval(4.0), Ok(Duration::from_millis(250)));
+    assert_eq!(setpoint_interval(50.0), Ok(Duration::from_millis(20)));
+    assert_eq!(setpoint_interval(3.0), Ok(Duration::from_nanos(333_333_333)));
+    for &rate in &[0.0, -1.0, 51.0, ::std::f32::NAN, ::std::f32::INFINITY] {
+        assert!(setpoint_interval(rate).is_err());
+    }
+}
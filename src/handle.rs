@@ -0,0 +1,167 @@
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Sender, TryRecvError};
+use std::thread;
+use std::time::Duration;
+use eventual::{Async, AsyncError, Future};
+
+use connection::VehicleConnection;
+use guided::{GlobalTarget, VelocityFrame, VelocityStream};
+use param::{ParamError, ParamValue};
+use state::Velocity;
+use streams::StreamProfile;
+use vehicle::{LocationLocal, Vehicle, VehicleMode};
+
+type VehicleCall = Box<FnMut(&mut Vehicle) + Send>;
+
+// How long the vehicle thread waits for telemetry before checking for calls.
+const POLL_INTERVAL_MS: u64 = 10;
+
+// A handle to a `Vehicle` running on its own thread. The handle can be
+// cloned and shared between threads; each call is run on the vehicle thread
+// in the order it was made.
+//
+// Commands returning a `Future` are queued without waiting and may be made
+// from anywhere, including attribute and parameter listeners. `with` and the
+// other calls returning values block until the vehicle thread has run them,
+// which takes until the first heartbeat while the vehicle initializes, so
+// they must not be made from listeners, which already run there.
+#[derive(Clone)]
+pub struct VehicleHandle {
+    calls: Arc<Mutex<Sender<VehicleCall>>>,
+}
+
+impl VehicleHandle {
+    // Starts a thread that owns a new `Vehicle`, initializes it, and then
+    // processes incoming messages and calls until every handle is dropped.
+    pub fn spawn(conn: VehicleConnection) -> VehicleHandle {
+        let (tx, rx) = channel::<VehicleCall>();
+
+        thread::spawn(move || {
+            let mut vehicle = Vehicle::new(conn);
+            vehicle.init();
+
+            loop {
+                loop {
+                    match rx.try_recv() {
+                        Ok(mut call) => call(&mut vehicle),
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => return,
+                    }
+                }
+                vehicle.update_timeout(Duration::from_millis(POLL_INTERVAL_MS));
+            }
+        });
+
+        VehicleHandle { calls: Arc::new(Mutex::new(tx)) }
+    }
+
+    // Queues `f` to run on the vehicle thread without waiting for it. Unlike
+    // `with`, this is safe to call from the vehicle thread.
+    pub fn post<F>(&self, f: F)
+        where F: FnOnce(&mut Vehicle) + Send + 'static
+    {
+        let mut f = Some(f);
+        let _ = self.calls.lock().unwrap().send(Box::new(move |vehicle| {
+            if let Some(f) = f.take() {
+                f(vehicle);
+            }
+        }));
+    }
+
+    // Runs `f` on the vehicle thread and returns its result.
+    pub fn with<F, R>(&self, f: F) -> R
+        where F: FnOnce(&mut Vehicle) -> R + Send + 'static,
+              R: Send + 'static
+    {
+        let (tx, rx) = channel();
+        self.post(move |vehicle| {
+            let _ = tx.send(f(vehicle));
+        });
+        rx.recv().unwrap()
+    }
+
+    // Queues a command started by `f` and returns its result without
+    // waiting for the vehicle thread. The future is aborted if the vehicle
+    // thread exits before the command completes.
+    pub fn command<F, T, E>(&self, f: F) -> Future<T, E>
+        where F: FnOnce(&mut Vehicle) -> Future<T, E> + Send + 'static,
+              T: Send + 'static,
+              E: Send + 'static
+    {
+        let (tx, future) = Future::<T, E>::pair();
+        self.post(move |vehicle| {
+            f(vehicle).receive(move |result| {
+                match result {
+                    Ok(value) => tx.complete(value),
+                    Err(AsyncError::Failed(e)) => tx.fail(e),
+                    Err(AsyncError::Aborted) => {}
+                }
+            });
+        });
+        future
+    }
+
+    pub fn set_mode(&self, mode: VehicleMode) -> Future<(), ()> {
+        self.command(move |v| v.set_mode(mode))
+    }
+
+    pub fn arm(&self) -> Future<(), ()> {
+        self.command(|v| v.arm())
+    }
+
+    pub fn disarm(&self) -> Future<(), ()> {
+        self.command(|v| v.disarm())
+    }
+
+    pub fn takeoff(&self, target_alt: f32) -> Future<(), ()> {
+        self.command(move |v| v.takeoff(target_alt))
+    }
+
+    pub fn set_airspeed(&self) -> Future<(), ()> {
+        self.command(|v| v.set_airspeed())
+    }
+
+    pub fn goto(&self, target: LocationLocal) -> Future<(), ()> {
+        self.command(move |v| v.goto(target))
+    }
+
+    pub fn goto_global<T: Into<GlobalTarget>>(&self, target: T, radius: f32) -> Future<(), ()> {
+        let target = target.into();
+        self.command(move |v| v.goto_global(target, radius))
+    }
+
+    pub fn send_velocity(&self, frame: VelocityFrame, velocity: Velocity, yaw_rate: Option<f32>) {
+        self.post(move |v| v.send_velocity(frame, velocity, yaw_rate))
+    }
+
+    pub fn stream_velocity(&self,
+                           frame: VelocityFrame,
+                           velocity: Velocity,
+                           yaw_rate: Option<f32>,
+                           rate_hz: f32)
+                           -> Result<VelocityStream, String> {
+        self.with(move |v| v.stream_velocity(frame, velocity, yaw_rate, rate_hz))
+    }
+
+    pub fn wait_alt(&self, target_alt: f32) -> Future<(), ()> {
+        self.command(move |v| v.wait_alt(target_alt))
+    }
+
+    pub fn set_message_interval(&self, message_id: u8, interval_us: i32) -> Future<(), ()> {
+        self.command(move |v| v.set_message_interval(message_id, interval_us))
+    }
+
+    pub fn apply_stream_profile(&self, profile: StreamProfile) -> Future<(), ()> {
+        self.command(move |v| v.apply_stream_profile(&profile))
+    }
+
+    pub fn get_parameter(&self, name: &str) -> Option<ParamValue> {
+        let name: String = name.into();
+        self.with(move |v| v.parameters.get(&name))
+    }
+
+    pub fn set_parameter(&self, name: &str, value: f32) -> Future<(), ParamError> {
+        let name: String = name.into();
+        self.command(move |v| v.parameters.set(&name, value))
+    }
+}
@@ -6,17 +6,34 @@ extern crate bytes;
 extern crate crc16;
 extern crate eventual;
 extern crate bit_vec;
+extern crate futures_core;
 
 pub mod mavlink;
 pub mod vehicle;
 pub mod parser;
 pub mod connection;
+pub mod mission;
+pub mod fence;
+pub mod rally;
+pub mod param;
+pub mod paramfile;
+pub mod parammeta;
+pub mod state;
+pub mod handle;
+pub mod async_api;
+pub mod tlog;
+pub mod stats;
+pub mod streams;
+pub mod guided;
+pub mod points;
 
-use connection::{VehicleConnection, DkHandler};
+use connection::{VehicleConnection, DkHandler, HEARTBEAT_INTERVAL_MS, LINK_TIMEOUT_MS, MAV_TYPE_GCS};
 use std::net::SocketAddr;
 use std::sync::mpsc::channel;
 use mio::tcp::TcpStream;
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::AtomicUsize;
 use std::thread;
 
 pub fn connect(address: SocketAddr) -> VehicleConnection {
@@ -35,6 +52,10 @@ pub fn connect(address: SocketAddr) -> VehicleConnection {
 
     let (tx, rx) = channel();
     let vehicle_tx = event_loop.channel();
+    let systems = Arc::new(Mutex::new(BTreeSet::new()));
+    let handler_systems = systems.clone();
+    let stats = Arc::new(Mutex::new(stats::LinkStats::new()));
+    let handler_stats = stats.clone();
 
     thread::spawn(move || {
         println!("running pingpong socket");
@@ -43,16 +64,34 @@ pub fn connect(address: SocketAddr) -> VehicleConnection {
             buf: vec![],
             vehicle_tx: tx,
             watchers: vec![],
+            routes: HashMap::new(),
+            systems: handler_systems,
+            stats: handler_stats,
+            corks: 0,
+            heartbeat: None,
+            heartbeats: HashMap::new(),
+            lost: BTreeSet::new(),
+            link_timeout_ns: LINK_TIMEOUT_MS * 1_000_000,
         };
         handler.register(&mut event_loop);
+        event_loop.timeout_ms((), HEARTBEAT_INTERVAL_MS).unwrap();
         event_loop.run(&mut handler).unwrap();
     });
 
     return VehicleConnection {
         tx: vehicle_tx,
         rx: rx,
-        msg_id: 0,
+        msg_id: Arc::new(AtomicUsize::new(0)),
         started: false,
         buffer: VecDeque::new(),
+        system_id: None,
+        target_system: 0,
+        target_component: 0,
+        source_system: 255,
+        source_component: 0,
+        source_type: MAV_TYPE_GCS,
+        systems: systems,
+        stats: stats,
+        events: VecDeque::new(),
     };
 }
@@ -9,6 +9,21 @@ pub trait Parsable {
     fn serialize(&self) -> Vec<u8>;
 }
 
+// The value of a message field, for printing and serializing messages
+// without matching on each type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MavValue {
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Array(Vec<MavValue>),
+}
+
+fn text(buf: &[u8]) -> String {
+    buf.iter().take_while(|&&x| x != 0).map(|&x| x as char).collect()
+}
+
 #[derive(Clone, Debug)]
 pub struct HEARTBEAT_DATA {
     pub custom_mode: u32,
@@ -43,6 +58,19 @@ impl Parsable for HEARTBEAT_DATA {
     }
 }
 
+impl HEARTBEAT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("custom_mode", MavValue::UInt(self.custom_mode as u64)),
+            ("type", MavValue::UInt(self.mavtype as u64)),
+            ("autopilot", MavValue::UInt(self.autopilot as u64)),
+            ("base_mode", MavValue::UInt(self.base_mode as u64)),
+            ("system_status", MavValue::UInt(self.system_status as u64)),
+            ("mavlink_version", MavValue::UInt(self.mavlink_version as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SYS_STATUS_DATA {
     pub onboard_control_sensors_present: u32,
@@ -98,6 +126,26 @@ impl Parsable for SYS_STATUS_DATA {
     }
 }
 
+impl SYS_STATUS_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("onboard_control_sensors_present", MavValue::UInt(self.onboard_control_sensors_present as u64)),
+            ("onboard_control_sensors_enabled", MavValue::UInt(self.onboard_control_sensors_enabled as u64)),
+            ("onboard_control_sensors_health", MavValue::UInt(self.onboard_control_sensors_health as u64)),
+            ("load", MavValue::UInt(self.load as u64)),
+            ("voltage_battery", MavValue::UInt(self.voltage_battery as u64)),
+            ("current_battery", MavValue::Int(self.current_battery as i64)),
+            ("drop_rate_comm", MavValue::UInt(self.drop_rate_comm as u64)),
+            ("errors_comm", MavValue::UInt(self.errors_comm as u64)),
+            ("errors_count1", MavValue::UInt(self.errors_count1 as u64)),
+            ("errors_count2", MavValue::UInt(self.errors_count2 as u64)),
+            ("errors_count3", MavValue::UInt(self.errors_count3 as u64)),
+            ("errors_count4", MavValue::UInt(self.errors_count4 as u64)),
+            ("battery_remaining", MavValue::Int(self.battery_remaining as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SYSTEM_TIME_DATA {
     pub time_unix_usec: u64,
@@ -120,6 +168,15 @@ impl Parsable for SYSTEM_TIME_DATA {
     }
 }
 
+impl SYSTEM_TIME_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_unix_usec", MavValue::UInt(self.time_unix_usec)),
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PING_DATA {
     pub time_usec: u64,
@@ -148,6 +205,17 @@ impl Parsable for PING_DATA {
     }
 }
 
+impl PING_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("seq", MavValue::UInt(self.seq as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CHANGE_OPERATOR_CONTROL_DATA {
     pub target_system: u8,
@@ -226,6 +294,17 @@ impl Parsable for CHANGE_OPERATOR_CONTROL_DATA {
     }
 }
 
+impl CHANGE_OPERATOR_CONTROL_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("control_request", MavValue::UInt(self.control_request as u64)),
+            ("version", MavValue::UInt(self.version as u64)),
+            ("passkey", MavValue::Text(text(&self.passkey))),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CHANGE_OPERATOR_CONTROL_ACK_DATA {
     pub gcs_system_id: u8,
@@ -251,6 +330,16 @@ impl Parsable for CHANGE_OPERATOR_CONTROL_ACK_DATA {
     }
 }
 
+impl CHANGE_OPERATOR_CONTROL_ACK_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("gcs_system_id", MavValue::UInt(self.gcs_system_id as u64)),
+            ("control_request", MavValue::UInt(self.control_request as u64)),
+            ("ack", MavValue::UInt(self.ack as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AUTH_KEY_DATA {
     pub key: Vec<u8>, // 32
@@ -334,6 +423,14 @@ impl Parsable for AUTH_KEY_DATA {
     }
 }
 
+impl AUTH_KEY_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("key", MavValue::Text(text(&self.key))),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SET_MODE_DATA {
     pub custom_mode: u32,
@@ -359,6 +456,16 @@ impl Parsable for SET_MODE_DATA {
     }
 }
 
+impl SET_MODE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("custom_mode", MavValue::UInt(self.custom_mode as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("base_mode", MavValue::UInt(self.base_mode as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PARAM_REQUEST_READ_DATA {
     pub param_index: i16,
@@ -419,6 +526,17 @@ impl Parsable for PARAM_REQUEST_READ_DATA {
     }
 }
 
+impl PARAM_REQUEST_READ_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("param_index", MavValue::Int(self.param_index as i64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("param_id", MavValue::Text(text(&self.param_id))),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PARAM_REQUEST_LIST_DATA {
     pub target_system: u8,
@@ -441,6 +559,15 @@ impl Parsable for PARAM_REQUEST_LIST_DATA {
     }
 }
 
+impl PARAM_REQUEST_LIST_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PARAM_VALUE_DATA {
     pub param_value: f32,
@@ -504,6 +631,18 @@ impl Parsable for PARAM_VALUE_DATA {
     }
 }
 
+impl PARAM_VALUE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("param_value", MavValue::Float(self.param_value as f64)),
+            ("param_count", MavValue::UInt(self.param_count as u64)),
+            ("param_index", MavValue::UInt(self.param_index as u64)),
+            ("param_id", MavValue::Text(text(&self.param_id))),
+            ("param_type", MavValue::UInt(self.param_type as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PARAM_SET_DATA {
     pub param_value: f32,
@@ -567,6 +706,18 @@ impl Parsable for PARAM_SET_DATA {
     }
 }
 
+impl PARAM_SET_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("param_value", MavValue::Float(self.param_value as f64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("param_id", MavValue::Text(text(&self.param_id))),
+            ("param_type", MavValue::UInt(self.param_type as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GPS_RAW_INT_DATA {
     pub time_usec: u64,
@@ -613,6 +764,23 @@ impl Parsable for GPS_RAW_INT_DATA {
     }
 }
 
+impl GPS_RAW_INT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+            ("alt", MavValue::Int(self.alt as i64)),
+            ("eph", MavValue::UInt(self.eph as u64)),
+            ("epv", MavValue::UInt(self.epv as u64)),
+            ("vel", MavValue::UInt(self.vel as u64)),
+            ("cog", MavValue::UInt(self.cog as u64)),
+            ("fix_type", MavValue::UInt(self.fix_type as u64)),
+            ("satellites_visible", MavValue::UInt(self.satellites_visible as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GPS_STATUS_DATA {
     pub satellites_visible: u8,
@@ -847,6 +1015,19 @@ impl Parsable for GPS_STATUS_DATA {
     }
 }
 
+impl GPS_STATUS_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("satellites_visible", MavValue::UInt(self.satellites_visible as u64)),
+            ("satellite_prn", MavValue::Array(self.satellite_prn.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+            ("satellite_used", MavValue::Array(self.satellite_used.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+            ("satellite_elevation", MavValue::Array(self.satellite_elevation.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+            ("satellite_azimuth", MavValue::Array(self.satellite_azimuth.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+            ("satellite_snr", MavValue::Array(self.satellite_snr.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SCALED_IMU_DATA {
     pub time_boot_ms: u32,
@@ -893,6 +1074,23 @@ impl Parsable for SCALED_IMU_DATA {
     }
 }
 
+impl SCALED_IMU_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("xacc", MavValue::Int(self.xacc as i64)),
+            ("yacc", MavValue::Int(self.yacc as i64)),
+            ("zacc", MavValue::Int(self.zacc as i64)),
+            ("xgyro", MavValue::Int(self.xgyro as i64)),
+            ("ygyro", MavValue::Int(self.ygyro as i64)),
+            ("zgyro", MavValue::Int(self.zgyro as i64)),
+            ("xmag", MavValue::Int(self.xmag as i64)),
+            ("ymag", MavValue::Int(self.ymag as i64)),
+            ("zmag", MavValue::Int(self.zmag as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RAW_IMU_DATA {
     pub time_usec: u64,
@@ -939,6 +1137,23 @@ impl Parsable for RAW_IMU_DATA {
     }
 }
 
+impl RAW_IMU_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("xacc", MavValue::Int(self.xacc as i64)),
+            ("yacc", MavValue::Int(self.yacc as i64)),
+            ("zacc", MavValue::Int(self.zacc as i64)),
+            ("xgyro", MavValue::Int(self.xgyro as i64)),
+            ("ygyro", MavValue::Int(self.ygyro as i64)),
+            ("zgyro", MavValue::Int(self.zgyro as i64)),
+            ("xmag", MavValue::Int(self.xmag as i64)),
+            ("ymag", MavValue::Int(self.ymag as i64)),
+            ("zmag", MavValue::Int(self.zmag as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RAW_PRESSURE_DATA {
     pub time_usec: u64,
@@ -970,6 +1185,18 @@ impl Parsable for RAW_PRESSURE_DATA {
     }
 }
 
+impl RAW_PRESSURE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("press_abs", MavValue::Int(self.press_abs as i64)),
+            ("press_diff1", MavValue::Int(self.press_diff1 as i64)),
+            ("press_diff2", MavValue::Int(self.press_diff2 as i64)),
+            ("temperature", MavValue::Int(self.temperature as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SCALED_PRESSURE_DATA {
     pub time_boot_ms: u32,
@@ -998,6 +1225,17 @@ impl Parsable for SCALED_PRESSURE_DATA {
     }
 }
 
+impl SCALED_PRESSURE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("press_abs", MavValue::Float(self.press_abs as f64)),
+            ("press_diff", MavValue::Float(self.press_diff as f64)),
+            ("temperature", MavValue::Int(self.temperature as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ATTITUDE_DATA {
     pub time_boot_ms: u32,
@@ -1035,6 +1273,20 @@ impl Parsable for ATTITUDE_DATA {
     }
 }
 
+impl ATTITUDE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("roll", MavValue::Float(self.roll as f64)),
+            ("pitch", MavValue::Float(self.pitch as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+            ("rollspeed", MavValue::Float(self.rollspeed as f64)),
+            ("pitchspeed", MavValue::Float(self.pitchspeed as f64)),
+            ("yawspeed", MavValue::Float(self.yawspeed as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ATTITUDE_QUATERNION_DATA {
     pub time_boot_ms: u32,
@@ -1075,6 +1327,21 @@ impl Parsable for ATTITUDE_QUATERNION_DATA {
     }
 }
 
+impl ATTITUDE_QUATERNION_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("q1", MavValue::Float(self.q1 as f64)),
+            ("q2", MavValue::Float(self.q2 as f64)),
+            ("q3", MavValue::Float(self.q3 as f64)),
+            ("q4", MavValue::Float(self.q4 as f64)),
+            ("rollspeed", MavValue::Float(self.rollspeed as f64)),
+            ("pitchspeed", MavValue::Float(self.pitchspeed as f64)),
+            ("yawspeed", MavValue::Float(self.yawspeed as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LOCAL_POSITION_NED_DATA {
     pub time_boot_ms: u32,
@@ -1112,6 +1379,20 @@ impl Parsable for LOCAL_POSITION_NED_DATA {
     }
 }
 
+impl LOCAL_POSITION_NED_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("vx", MavValue::Float(self.vx as f64)),
+            ("vy", MavValue::Float(self.vy as f64)),
+            ("vz", MavValue::Float(self.vz as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GLOBAL_POSITION_INT_DATA {
     pub time_boot_ms: u32,
@@ -1155,6 +1436,22 @@ impl Parsable for GLOBAL_POSITION_INT_DATA {
     }
 }
 
+impl GLOBAL_POSITION_INT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+            ("alt", MavValue::Int(self.alt as i64)),
+            ("relative_alt", MavValue::Int(self.relative_alt as i64)),
+            ("vx", MavValue::Int(self.vx as i64)),
+            ("vy", MavValue::Int(self.vy as i64)),
+            ("vz", MavValue::Int(self.vz as i64)),
+            ("hdg", MavValue::UInt(self.hdg as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RC_CHANNELS_SCALED_DATA {
     pub time_boot_ms: u32,
@@ -1204,6 +1501,24 @@ impl Parsable for RC_CHANNELS_SCALED_DATA {
     }
 }
 
+impl RC_CHANNELS_SCALED_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("chan1_scaled", MavValue::Int(self.chan1_scaled as i64)),
+            ("chan2_scaled", MavValue::Int(self.chan2_scaled as i64)),
+            ("chan3_scaled", MavValue::Int(self.chan3_scaled as i64)),
+            ("chan4_scaled", MavValue::Int(self.chan4_scaled as i64)),
+            ("chan5_scaled", MavValue::Int(self.chan5_scaled as i64)),
+            ("chan6_scaled", MavValue::Int(self.chan6_scaled as i64)),
+            ("chan7_scaled", MavValue::Int(self.chan7_scaled as i64)),
+            ("chan8_scaled", MavValue::Int(self.chan8_scaled as i64)),
+            ("port", MavValue::UInt(self.port as u64)),
+            ("rssi", MavValue::UInt(self.rssi as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RC_CHANNELS_RAW_DATA {
     pub time_boot_ms: u32,
@@ -1253,6 +1568,24 @@ impl Parsable for RC_CHANNELS_RAW_DATA {
     }
 }
 
+impl RC_CHANNELS_RAW_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("chan1_raw", MavValue::UInt(self.chan1_raw as u64)),
+            ("chan2_raw", MavValue::UInt(self.chan2_raw as u64)),
+            ("chan3_raw", MavValue::UInt(self.chan3_raw as u64)),
+            ("chan4_raw", MavValue::UInt(self.chan4_raw as u64)),
+            ("chan5_raw", MavValue::UInt(self.chan5_raw as u64)),
+            ("chan6_raw", MavValue::UInt(self.chan6_raw as u64)),
+            ("chan7_raw", MavValue::UInt(self.chan7_raw as u64)),
+            ("chan8_raw", MavValue::UInt(self.chan8_raw as u64)),
+            ("port", MavValue::UInt(self.port as u64)),
+            ("rssi", MavValue::UInt(self.rssi as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SERVO_OUTPUT_RAW_DATA {
     pub time_usec: u32,
@@ -1299,6 +1632,23 @@ impl Parsable for SERVO_OUTPUT_RAW_DATA {
     }
 }
 
+impl SERVO_OUTPUT_RAW_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec as u64)),
+            ("servo1_raw", MavValue::UInt(self.servo1_raw as u64)),
+            ("servo2_raw", MavValue::UInt(self.servo2_raw as u64)),
+            ("servo3_raw", MavValue::UInt(self.servo3_raw as u64)),
+            ("servo4_raw", MavValue::UInt(self.servo4_raw as u64)),
+            ("servo5_raw", MavValue::UInt(self.servo5_raw as u64)),
+            ("servo6_raw", MavValue::UInt(self.servo6_raw as u64)),
+            ("servo7_raw", MavValue::UInt(self.servo7_raw as u64)),
+            ("servo8_raw", MavValue::UInt(self.servo8_raw as u64)),
+            ("port", MavValue::UInt(self.port as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_REQUEST_PARTIAL_LIST_DATA {
     pub start_index: i16,
@@ -1327,6 +1677,17 @@ impl Parsable for MISSION_REQUEST_PARTIAL_LIST_DATA {
     }
 }
 
+impl MISSION_REQUEST_PARTIAL_LIST_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("start_index", MavValue::Int(self.start_index as i64)),
+            ("end_index", MavValue::Int(self.end_index as i64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_WRITE_PARTIAL_LIST_DATA {
     pub start_index: i16,
@@ -1355,6 +1716,17 @@ impl Parsable for MISSION_WRITE_PARTIAL_LIST_DATA {
     }
 }
 
+impl MISSION_WRITE_PARTIAL_LIST_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("start_index", MavValue::Int(self.start_index as i64)),
+            ("end_index", MavValue::Int(self.end_index as i64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_ITEM_DATA {
     pub param1: f32,
@@ -1413,6 +1785,27 @@ impl Parsable for MISSION_ITEM_DATA {
     }
 }
 
+impl MISSION_ITEM_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("param1", MavValue::Float(self.param1 as f64)),
+            ("param2", MavValue::Float(self.param2 as f64)),
+            ("param3", MavValue::Float(self.param3 as f64)),
+            ("param4", MavValue::Float(self.param4 as f64)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("seq", MavValue::UInt(self.seq as u64)),
+            ("command", MavValue::UInt(self.command as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("frame", MavValue::UInt(self.frame as u64)),
+            ("current", MavValue::UInt(self.current as u64)),
+            ("autocontinue", MavValue::UInt(self.autocontinue as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_REQUEST_DATA {
     pub seq: u16,
@@ -1438,6 +1831,16 @@ impl Parsable for MISSION_REQUEST_DATA {
     }
 }
 
+impl MISSION_REQUEST_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("seq", MavValue::UInt(self.seq as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_SET_CURRENT_DATA {
     pub seq: u16,
@@ -1463,6 +1866,16 @@ impl Parsable for MISSION_SET_CURRENT_DATA {
     }
 }
 
+impl MISSION_SET_CURRENT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("seq", MavValue::UInt(self.seq as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_CURRENT_DATA {
     pub seq: u16,
@@ -1480,6 +1893,14 @@ impl Parsable for MISSION_CURRENT_DATA {
     }
 }
 
+impl MISSION_CURRENT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("seq", MavValue::UInt(self.seq as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_REQUEST_LIST_DATA {
     pub target_system: u8,
@@ -1502,6 +1923,15 @@ impl Parsable for MISSION_REQUEST_LIST_DATA {
     }
 }
 
+impl MISSION_REQUEST_LIST_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_COUNT_DATA {
     pub count: u16,
@@ -1527,6 +1957,16 @@ impl Parsable for MISSION_COUNT_DATA {
     }
 }
 
+impl MISSION_COUNT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("count", MavValue::UInt(self.count as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_CLEAR_ALL_DATA {
     pub target_system: u8,
@@ -1549,6 +1989,15 @@ impl Parsable for MISSION_CLEAR_ALL_DATA {
     }
 }
 
+impl MISSION_CLEAR_ALL_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_ITEM_REACHED_DATA {
     pub seq: u16,
@@ -1566,6 +2015,14 @@ impl Parsable for MISSION_ITEM_REACHED_DATA {
     }
 }
 
+impl MISSION_ITEM_REACHED_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("seq", MavValue::UInt(self.seq as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_ACK_DATA {
     pub target_system: u8,
@@ -1591,6 +2048,16 @@ impl Parsable for MISSION_ACK_DATA {
     }
 }
 
+impl MISSION_ACK_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("type", MavValue::UInt(self.mavtype as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SET_GPS_GLOBAL_ORIGIN_DATA {
     pub latitude: i32,
@@ -1619,6 +2086,17 @@ impl Parsable for SET_GPS_GLOBAL_ORIGIN_DATA {
     }
 }
 
+impl SET_GPS_GLOBAL_ORIGIN_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("latitude", MavValue::Int(self.latitude as i64)),
+            ("longitude", MavValue::Int(self.longitude as i64)),
+            ("altitude", MavValue::Int(self.altitude as i64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GPS_GLOBAL_ORIGIN_DATA {
     pub latitude: i32,
@@ -1644,6 +2122,16 @@ impl Parsable for GPS_GLOBAL_ORIGIN_DATA {
     }
 }
 
+impl GPS_GLOBAL_ORIGIN_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("latitude", MavValue::Int(self.latitude as i64)),
+            ("longitude", MavValue::Int(self.longitude as i64)),
+            ("altitude", MavValue::Int(self.altitude as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PARAM_MAP_RC_DATA {
     pub param_value0: f32,
@@ -1719,6 +2207,22 @@ impl Parsable for PARAM_MAP_RC_DATA {
     }
 }
 
+impl PARAM_MAP_RC_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("param_value0", MavValue::Float(self.param_value0 as f64)),
+            ("scale", MavValue::Float(self.scale as f64)),
+            ("param_value_min", MavValue::Float(self.param_value_min as f64)),
+            ("param_value_max", MavValue::Float(self.param_value_max as f64)),
+            ("param_index", MavValue::Int(self.param_index as i64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("param_id", MavValue::Text(text(&self.param_id))),
+            ("parameter_rc_channel_index", MavValue::UInt(self.parameter_rc_channel_index as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SAFETY_SET_ALLOWED_AREA_DATA {
     pub p1x: f32,
@@ -1762,6 +2266,22 @@ impl Parsable for SAFETY_SET_ALLOWED_AREA_DATA {
     }
 }
 
+impl SAFETY_SET_ALLOWED_AREA_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("p1x", MavValue::Float(self.p1x as f64)),
+            ("p1y", MavValue::Float(self.p1y as f64)),
+            ("p1z", MavValue::Float(self.p1z as f64)),
+            ("p2x", MavValue::Float(self.p2x as f64)),
+            ("p2y", MavValue::Float(self.p2y as f64)),
+            ("p2z", MavValue::Float(self.p2z as f64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("frame", MavValue::UInt(self.frame as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SAFETY_ALLOWED_AREA_DATA {
     pub p1x: f32,
@@ -1799,6 +2319,20 @@ impl Parsable for SAFETY_ALLOWED_AREA_DATA {
     }
 }
 
+impl SAFETY_ALLOWED_AREA_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("p1x", MavValue::Float(self.p1x as f64)),
+            ("p1y", MavValue::Float(self.p1y as f64)),
+            ("p1z", MavValue::Float(self.p1z as f64)),
+            ("p2x", MavValue::Float(self.p2x as f64)),
+            ("p2y", MavValue::Float(self.p2y as f64)),
+            ("p2z", MavValue::Float(self.p2z as f64)),
+            ("frame", MavValue::UInt(self.frame as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ATTITUDE_QUATERNION_COV_DATA {
     pub time_boot_ms: u32,
@@ -1859,6 +2393,19 @@ impl Parsable for ATTITUDE_QUATERNION_COV_DATA {
     }
 }
 
+impl ATTITUDE_QUATERNION_COV_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("q", MavValue::Array(self.q.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("rollspeed", MavValue::Float(self.rollspeed as f64)),
+            ("pitchspeed", MavValue::Float(self.pitchspeed as f64)),
+            ("yawspeed", MavValue::Float(self.yawspeed as f64)),
+            ("covariance", MavValue::Array(self.covariance.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct NAV_CONTROLLER_OUTPUT_DATA {
     pub nav_roll: f32,
@@ -1899,6 +2446,21 @@ impl Parsable for NAV_CONTROLLER_OUTPUT_DATA {
     }
 }
 
+impl NAV_CONTROLLER_OUTPUT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("nav_roll", MavValue::Float(self.nav_roll as f64)),
+            ("nav_pitch", MavValue::Float(self.nav_pitch as f64)),
+            ("alt_error", MavValue::Float(self.alt_error as f64)),
+            ("aspd_error", MavValue::Float(self.aspd_error as f64)),
+            ("xtrack_error", MavValue::Float(self.xtrack_error as f64)),
+            ("nav_bearing", MavValue::Int(self.nav_bearing as i64)),
+            ("target_bearing", MavValue::Int(self.target_bearing as i64)),
+            ("wp_dist", MavValue::UInt(self.wp_dist as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GLOBAL_POSITION_INT_COV_DATA {
     pub time_utc: u64,
@@ -2020,6 +2582,24 @@ impl Parsable for GLOBAL_POSITION_INT_COV_DATA {
     }
 }
 
+impl GLOBAL_POSITION_INT_COV_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_utc", MavValue::UInt(self.time_utc)),
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+            ("alt", MavValue::Int(self.alt as i64)),
+            ("relative_alt", MavValue::Int(self.relative_alt as i64)),
+            ("vx", MavValue::Float(self.vx as f64)),
+            ("vy", MavValue::Float(self.vy as f64)),
+            ("vz", MavValue::Float(self.vz as f64)),
+            ("covariance", MavValue::Array(self.covariance.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("estimator_type", MavValue::UInt(self.estimator_type as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LOCAL_POSITION_NED_COV_DATA {
     pub time_utc: u64,
@@ -2165,6 +2745,26 @@ impl Parsable for LOCAL_POSITION_NED_COV_DATA {
     }
 }
 
+impl LOCAL_POSITION_NED_COV_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_utc", MavValue::UInt(self.time_utc)),
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("vx", MavValue::Float(self.vx as f64)),
+            ("vy", MavValue::Float(self.vy as f64)),
+            ("vz", MavValue::Float(self.vz as f64)),
+            ("ax", MavValue::Float(self.ax as f64)),
+            ("ay", MavValue::Float(self.ay as f64)),
+            ("az", MavValue::Float(self.az as f64)),
+            ("covariance", MavValue::Array(self.covariance.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("estimator_type", MavValue::UInt(self.estimator_type as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RC_CHANNELS_DATA {
     pub time_boot_ms: u32,
@@ -2244,6 +2844,34 @@ impl Parsable for RC_CHANNELS_DATA {
     }
 }
 
+impl RC_CHANNELS_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("chan1_raw", MavValue::UInt(self.chan1_raw as u64)),
+            ("chan2_raw", MavValue::UInt(self.chan2_raw as u64)),
+            ("chan3_raw", MavValue::UInt(self.chan3_raw as u64)),
+            ("chan4_raw", MavValue::UInt(self.chan4_raw as u64)),
+            ("chan5_raw", MavValue::UInt(self.chan5_raw as u64)),
+            ("chan6_raw", MavValue::UInt(self.chan6_raw as u64)),
+            ("chan7_raw", MavValue::UInt(self.chan7_raw as u64)),
+            ("chan8_raw", MavValue::UInt(self.chan8_raw as u64)),
+            ("chan9_raw", MavValue::UInt(self.chan9_raw as u64)),
+            ("chan10_raw", MavValue::UInt(self.chan10_raw as u64)),
+            ("chan11_raw", MavValue::UInt(self.chan11_raw as u64)),
+            ("chan12_raw", MavValue::UInt(self.chan12_raw as u64)),
+            ("chan13_raw", MavValue::UInt(self.chan13_raw as u64)),
+            ("chan14_raw", MavValue::UInt(self.chan14_raw as u64)),
+            ("chan15_raw", MavValue::UInt(self.chan15_raw as u64)),
+            ("chan16_raw", MavValue::UInt(self.chan16_raw as u64)),
+            ("chan17_raw", MavValue::UInt(self.chan17_raw as u64)),
+            ("chan18_raw", MavValue::UInt(self.chan18_raw as u64)),
+            ("chancount", MavValue::UInt(self.chancount as u64)),
+            ("rssi", MavValue::UInt(self.rssi as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct REQUEST_DATA_STREAM_DATA {
     pub req_message_rate: u16,
@@ -2275,6 +2903,18 @@ impl Parsable for REQUEST_DATA_STREAM_DATA {
     }
 }
 
+impl REQUEST_DATA_STREAM_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("req_message_rate", MavValue::UInt(self.req_message_rate as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("req_stream_id", MavValue::UInt(self.req_stream_id as u64)),
+            ("start_stop", MavValue::UInt(self.start_stop as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DATA_STREAM_DATA {
     pub message_rate: u16,
@@ -2300,6 +2940,16 @@ impl Parsable for DATA_STREAM_DATA {
     }
 }
 
+impl DATA_STREAM_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("message_rate", MavValue::UInt(self.message_rate as u64)),
+            ("stream_id", MavValue::UInt(self.stream_id as u64)),
+            ("on_off", MavValue::UInt(self.on_off as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MANUAL_CONTROL_DATA {
     pub x: i16,
@@ -2334,6 +2984,19 @@ impl Parsable for MANUAL_CONTROL_DATA {
     }
 }
 
+impl MANUAL_CONTROL_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("x", MavValue::Int(self.x as i64)),
+            ("y", MavValue::Int(self.y as i64)),
+            ("z", MavValue::Int(self.z as i64)),
+            ("r", MavValue::Int(self.r as i64)),
+            ("buttons", MavValue::UInt(self.buttons as u64)),
+            ("target", MavValue::UInt(self.target as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RC_CHANNELS_OVERRIDE_DATA {
     pub chan1_raw: u16,
@@ -2380,6 +3043,23 @@ impl Parsable for RC_CHANNELS_OVERRIDE_DATA {
     }
 }
 
+impl RC_CHANNELS_OVERRIDE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("chan1_raw", MavValue::UInt(self.chan1_raw as u64)),
+            ("chan2_raw", MavValue::UInt(self.chan2_raw as u64)),
+            ("chan3_raw", MavValue::UInt(self.chan3_raw as u64)),
+            ("chan4_raw", MavValue::UInt(self.chan4_raw as u64)),
+            ("chan5_raw", MavValue::UInt(self.chan5_raw as u64)),
+            ("chan6_raw", MavValue::UInt(self.chan6_raw as u64)),
+            ("chan7_raw", MavValue::UInt(self.chan7_raw as u64)),
+            ("chan8_raw", MavValue::UInt(self.chan8_raw as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MISSION_ITEM_INT_DATA {
     pub param1: f32,
@@ -2438,6 +3118,27 @@ impl Parsable for MISSION_ITEM_INT_DATA {
     }
 }
 
+impl MISSION_ITEM_INT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("param1", MavValue::Float(self.param1 as f64)),
+            ("param2", MavValue::Float(self.param2 as f64)),
+            ("param3", MavValue::Float(self.param3 as f64)),
+            ("param4", MavValue::Float(self.param4 as f64)),
+            ("x", MavValue::Int(self.x as i64)),
+            ("y", MavValue::Int(self.y as i64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("seq", MavValue::UInt(self.seq as u64)),
+            ("command", MavValue::UInt(self.command as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("frame", MavValue::UInt(self.frame as u64)),
+            ("current", MavValue::UInt(self.current as u64)),
+            ("autocontinue", MavValue::UInt(self.autocontinue as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct VFR_HUD_DATA {
     pub airspeed: f32,
@@ -2472,6 +3173,19 @@ impl Parsable for VFR_HUD_DATA {
     }
 }
 
+impl VFR_HUD_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("airspeed", MavValue::Float(self.airspeed as f64)),
+            ("groundspeed", MavValue::Float(self.groundspeed as f64)),
+            ("alt", MavValue::Float(self.alt as f64)),
+            ("climb", MavValue::Float(self.climb as f64)),
+            ("heading", MavValue::Int(self.heading as i64)),
+            ("throttle", MavValue::UInt(self.throttle as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct COMMAND_INT_DATA {
     pub param1: f32,
@@ -2527,6 +3241,26 @@ impl Parsable for COMMAND_INT_DATA {
     }
 }
 
+impl COMMAND_INT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("param1", MavValue::Float(self.param1 as f64)),
+            ("param2", MavValue::Float(self.param2 as f64)),
+            ("param3", MavValue::Float(self.param3 as f64)),
+            ("param4", MavValue::Float(self.param4 as f64)),
+            ("x", MavValue::Int(self.x as i64)),
+            ("y", MavValue::Int(self.y as i64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("command", MavValue::UInt(self.command as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("frame", MavValue::UInt(self.frame as u64)),
+            ("current", MavValue::UInt(self.current as u64)),
+            ("autocontinue", MavValue::UInt(self.autocontinue as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct COMMAND_LONG_DATA {
     pub param1: f32,
@@ -2576,6 +3310,24 @@ impl Parsable for COMMAND_LONG_DATA {
     }
 }
 
+impl COMMAND_LONG_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("param1", MavValue::Float(self.param1 as f64)),
+            ("param2", MavValue::Float(self.param2 as f64)),
+            ("param3", MavValue::Float(self.param3 as f64)),
+            ("param4", MavValue::Float(self.param4 as f64)),
+            ("param5", MavValue::Float(self.param5 as f64)),
+            ("param6", MavValue::Float(self.param6 as f64)),
+            ("param7", MavValue::Float(self.param7 as f64)),
+            ("command", MavValue::UInt(self.command as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("confirmation", MavValue::UInt(self.confirmation as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct COMMAND_ACK_DATA {
     pub command: u16,
@@ -2598,6 +3350,15 @@ impl Parsable for COMMAND_ACK_DATA {
     }
 }
 
+impl COMMAND_ACK_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("command", MavValue::UInt(self.command as u64)),
+            ("result", MavValue::UInt(self.result as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MANUAL_SETPOINT_DATA {
     pub time_boot_ms: u32,
@@ -2635,6 +3396,20 @@ impl Parsable for MANUAL_SETPOINT_DATA {
     }
 }
 
+impl MANUAL_SETPOINT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("roll", MavValue::Float(self.roll as f64)),
+            ("pitch", MavValue::Float(self.pitch as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+            ("thrust", MavValue::Float(self.thrust as f64)),
+            ("mode_switch", MavValue::UInt(self.mode_switch as u64)),
+            ("manual_override_switch", MavValue::UInt(self.manual_override_switch as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SET_ATTITUDE_TARGET_DATA {
     pub time_boot_ms: u32,
@@ -2686,6 +3461,22 @@ impl Parsable for SET_ATTITUDE_TARGET_DATA {
     }
 }
 
+impl SET_ATTITUDE_TARGET_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("q", MavValue::Array(self.q.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("body_roll_rate", MavValue::Float(self.body_roll_rate as f64)),
+            ("body_pitch_rate", MavValue::Float(self.body_pitch_rate as f64)),
+            ("body_yaw_rate", MavValue::Float(self.body_yaw_rate as f64)),
+            ("thrust", MavValue::Float(self.thrust as f64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("type_mask", MavValue::UInt(self.type_mask as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ATTITUDE_TARGET_DATA {
     pub time_boot_ms: u32,
@@ -2731,6 +3522,20 @@ impl Parsable for ATTITUDE_TARGET_DATA {
     }
 }
 
+impl ATTITUDE_TARGET_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("q", MavValue::Array(self.q.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("body_roll_rate", MavValue::Float(self.body_roll_rate as f64)),
+            ("body_pitch_rate", MavValue::Float(self.body_pitch_rate as f64)),
+            ("body_yaw_rate", MavValue::Float(self.body_yaw_rate as f64)),
+            ("thrust", MavValue::Float(self.thrust as f64)),
+            ("type_mask", MavValue::UInt(self.type_mask as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SET_POSITION_TARGET_LOCAL_NED_DATA {
     pub time_boot_ms: u32,
@@ -2795,6 +3600,29 @@ impl Parsable for SET_POSITION_TARGET_LOCAL_NED_DATA {
     }
 }
 
+impl SET_POSITION_TARGET_LOCAL_NED_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("vx", MavValue::Float(self.vx as f64)),
+            ("vy", MavValue::Float(self.vy as f64)),
+            ("vz", MavValue::Float(self.vz as f64)),
+            ("afx", MavValue::Float(self.afx as f64)),
+            ("afy", MavValue::Float(self.afy as f64)),
+            ("afz", MavValue::Float(self.afz as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+            ("yaw_rate", MavValue::Float(self.yaw_rate as f64)),
+            ("type_mask", MavValue::UInt(self.type_mask as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("coordinate_frame", MavValue::UInt(self.coordinate_frame as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct POSITION_TARGET_LOCAL_NED_DATA {
     pub time_boot_ms: u32,
@@ -2853,6 +3681,27 @@ impl Parsable for POSITION_TARGET_LOCAL_NED_DATA {
     }
 }
 
+impl POSITION_TARGET_LOCAL_NED_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("vx", MavValue::Float(self.vx as f64)),
+            ("vy", MavValue::Float(self.vy as f64)),
+            ("vz", MavValue::Float(self.vz as f64)),
+            ("afx", MavValue::Float(self.afx as f64)),
+            ("afy", MavValue::Float(self.afy as f64)),
+            ("afz", MavValue::Float(self.afz as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+            ("yaw_rate", MavValue::Float(self.yaw_rate as f64)),
+            ("type_mask", MavValue::UInt(self.type_mask as u64)),
+            ("coordinate_frame", MavValue::UInt(self.coordinate_frame as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SET_POSITION_TARGET_GLOBAL_INT_DATA {
     pub time_boot_ms: u32,
@@ -2917,6 +3766,29 @@ impl Parsable for SET_POSITION_TARGET_GLOBAL_INT_DATA {
     }
 }
 
+impl SET_POSITION_TARGET_GLOBAL_INT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("lat_int", MavValue::Int(self.lat_int as i64)),
+            ("lon_int", MavValue::Int(self.lon_int as i64)),
+            ("alt", MavValue::Float(self.alt as f64)),
+            ("vx", MavValue::Float(self.vx as f64)),
+            ("vy", MavValue::Float(self.vy as f64)),
+            ("vz", MavValue::Float(self.vz as f64)),
+            ("afx", MavValue::Float(self.afx as f64)),
+            ("afy", MavValue::Float(self.afy as f64)),
+            ("afz", MavValue::Float(self.afz as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+            ("yaw_rate", MavValue::Float(self.yaw_rate as f64)),
+            ("type_mask", MavValue::UInt(self.type_mask as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("coordinate_frame", MavValue::UInt(self.coordinate_frame as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct POSITION_TARGET_GLOBAL_INT_DATA {
     pub time_boot_ms: u32,
@@ -2975,6 +3847,27 @@ impl Parsable for POSITION_TARGET_GLOBAL_INT_DATA {
     }
 }
 
+impl POSITION_TARGET_GLOBAL_INT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("lat_int", MavValue::Int(self.lat_int as i64)),
+            ("lon_int", MavValue::Int(self.lon_int as i64)),
+            ("alt", MavValue::Float(self.alt as f64)),
+            ("vx", MavValue::Float(self.vx as f64)),
+            ("vy", MavValue::Float(self.vy as f64)),
+            ("vz", MavValue::Float(self.vz as f64)),
+            ("afx", MavValue::Float(self.afx as f64)),
+            ("afy", MavValue::Float(self.afy as f64)),
+            ("afz", MavValue::Float(self.afz as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+            ("yaw_rate", MavValue::Float(self.yaw_rate as f64)),
+            ("type_mask", MavValue::UInt(self.type_mask as u64)),
+            ("coordinate_frame", MavValue::UInt(self.coordinate_frame as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LOCAL_POSITION_NED_SYSTEM_GLOBAL_OFFSET_DATA {
     pub time_boot_ms: u32,
@@ -3012,6 +3905,20 @@ impl Parsable for LOCAL_POSITION_NED_SYSTEM_GLOBAL_OFFSET_DATA {
     }
 }
 
+impl LOCAL_POSITION_NED_SYSTEM_GLOBAL_OFFSET_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("roll", MavValue::Float(self.roll as f64)),
+            ("pitch", MavValue::Float(self.pitch as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HIL_STATE_DATA {
     pub time_usec: u64,
@@ -3076,6 +3983,29 @@ impl Parsable for HIL_STATE_DATA {
     }
 }
 
+impl HIL_STATE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("roll", MavValue::Float(self.roll as f64)),
+            ("pitch", MavValue::Float(self.pitch as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+            ("rollspeed", MavValue::Float(self.rollspeed as f64)),
+            ("pitchspeed", MavValue::Float(self.pitchspeed as f64)),
+            ("yawspeed", MavValue::Float(self.yawspeed as f64)),
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+            ("alt", MavValue::Int(self.alt as i64)),
+            ("vx", MavValue::Int(self.vx as i64)),
+            ("vy", MavValue::Int(self.vy as i64)),
+            ("vz", MavValue::Int(self.vz as i64)),
+            ("xacc", MavValue::Int(self.xacc as i64)),
+            ("yacc", MavValue::Int(self.yacc as i64)),
+            ("zacc", MavValue::Int(self.zacc as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HIL_CONTROLS_DATA {
     pub time_usec: u64,
@@ -3125,6 +4055,24 @@ impl Parsable for HIL_CONTROLS_DATA {
     }
 }
 
+impl HIL_CONTROLS_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("roll_ailerons", MavValue::Float(self.roll_ailerons as f64)),
+            ("pitch_elevator", MavValue::Float(self.pitch_elevator as f64)),
+            ("yaw_rudder", MavValue::Float(self.yaw_rudder as f64)),
+            ("throttle", MavValue::Float(self.throttle as f64)),
+            ("aux1", MavValue::Float(self.aux1 as f64)),
+            ("aux2", MavValue::Float(self.aux2 as f64)),
+            ("aux3", MavValue::Float(self.aux3 as f64)),
+            ("aux4", MavValue::Float(self.aux4 as f64)),
+            ("mode", MavValue::UInt(self.mode as u64)),
+            ("nav_mode", MavValue::UInt(self.nav_mode as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HIL_RC_INPUTS_RAW_DATA {
     pub time_usec: u64,
@@ -3183,6 +4131,27 @@ impl Parsable for HIL_RC_INPUTS_RAW_DATA {
     }
 }
 
+impl HIL_RC_INPUTS_RAW_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("chan1_raw", MavValue::UInt(self.chan1_raw as u64)),
+            ("chan2_raw", MavValue::UInt(self.chan2_raw as u64)),
+            ("chan3_raw", MavValue::UInt(self.chan3_raw as u64)),
+            ("chan4_raw", MavValue::UInt(self.chan4_raw as u64)),
+            ("chan5_raw", MavValue::UInt(self.chan5_raw as u64)),
+            ("chan6_raw", MavValue::UInt(self.chan6_raw as u64)),
+            ("chan7_raw", MavValue::UInt(self.chan7_raw as u64)),
+            ("chan8_raw", MavValue::UInt(self.chan8_raw as u64)),
+            ("chan9_raw", MavValue::UInt(self.chan9_raw as u64)),
+            ("chan10_raw", MavValue::UInt(self.chan10_raw as u64)),
+            ("chan11_raw", MavValue::UInt(self.chan11_raw as u64)),
+            ("chan12_raw", MavValue::UInt(self.chan12_raw as u64)),
+            ("rssi", MavValue::UInt(self.rssi as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct OPTICAL_FLOW_DATA {
     pub time_usec: u64,
@@ -3223,6 +4192,21 @@ impl Parsable for OPTICAL_FLOW_DATA {
     }
 }
 
+impl OPTICAL_FLOW_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("flow_comp_m_x", MavValue::Float(self.flow_comp_m_x as f64)),
+            ("flow_comp_m_y", MavValue::Float(self.flow_comp_m_y as f64)),
+            ("ground_distance", MavValue::Float(self.ground_distance as f64)),
+            ("flow_x", MavValue::Int(self.flow_x as i64)),
+            ("flow_y", MavValue::Int(self.flow_y as i64)),
+            ("sensor_id", MavValue::UInt(self.sensor_id as u64)),
+            ("quality", MavValue::UInt(self.quality as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GLOBAL_VISION_POSITION_ESTIMATE_DATA {
     pub usec: u64,
@@ -3260,6 +4244,20 @@ impl Parsable for GLOBAL_VISION_POSITION_ESTIMATE_DATA {
     }
 }
 
+impl GLOBAL_VISION_POSITION_ESTIMATE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("usec", MavValue::UInt(self.usec)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("roll", MavValue::Float(self.roll as f64)),
+            ("pitch", MavValue::Float(self.pitch as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct VISION_POSITION_ESTIMATE_DATA {
     pub usec: u64,
@@ -3297,6 +4295,20 @@ impl Parsable for VISION_POSITION_ESTIMATE_DATA {
     }
 }
 
+impl VISION_POSITION_ESTIMATE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("usec", MavValue::UInt(self.usec)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("roll", MavValue::Float(self.roll as f64)),
+            ("pitch", MavValue::Float(self.pitch as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct VISION_SPEED_ESTIMATE_DATA {
     pub usec: u64,
@@ -3325,6 +4337,17 @@ impl Parsable for VISION_SPEED_ESTIMATE_DATA {
     }
 }
 
+impl VISION_SPEED_ESTIMATE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("usec", MavValue::UInt(self.usec)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct VICON_POSITION_ESTIMATE_DATA {
     pub usec: u64,
@@ -3362,6 +4385,20 @@ impl Parsable for VICON_POSITION_ESTIMATE_DATA {
     }
 }
 
+impl VICON_POSITION_ESTIMATE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("usec", MavValue::UInt(self.usec)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("roll", MavValue::Float(self.roll as f64)),
+            ("pitch", MavValue::Float(self.pitch as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HIGHRES_IMU_DATA {
     pub time_usec: u64,
@@ -3423,6 +4460,28 @@ impl Parsable for HIGHRES_IMU_DATA {
     }
 }
 
+impl HIGHRES_IMU_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("xacc", MavValue::Float(self.xacc as f64)),
+            ("yacc", MavValue::Float(self.yacc as f64)),
+            ("zacc", MavValue::Float(self.zacc as f64)),
+            ("xgyro", MavValue::Float(self.xgyro as f64)),
+            ("ygyro", MavValue::Float(self.ygyro as f64)),
+            ("zgyro", MavValue::Float(self.zgyro as f64)),
+            ("xmag", MavValue::Float(self.xmag as f64)),
+            ("ymag", MavValue::Float(self.ymag as f64)),
+            ("zmag", MavValue::Float(self.zmag as f64)),
+            ("abs_pressure", MavValue::Float(self.abs_pressure as f64)),
+            ("diff_pressure", MavValue::Float(self.diff_pressure as f64)),
+            ("pressure_alt", MavValue::Float(self.pressure_alt as f64)),
+            ("temperature", MavValue::Float(self.temperature as f64)),
+            ("fields_updated", MavValue::UInt(self.fields_updated as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct OPTICAL_FLOW_RAD_DATA {
     pub time_usec: u64,
@@ -3475,6 +4534,25 @@ impl Parsable for OPTICAL_FLOW_RAD_DATA {
     }
 }
 
+impl OPTICAL_FLOW_RAD_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("integration_time_us", MavValue::UInt(self.integration_time_us as u64)),
+            ("integrated_x", MavValue::Float(self.integrated_x as f64)),
+            ("integrated_y", MavValue::Float(self.integrated_y as f64)),
+            ("integrated_xgyro", MavValue::Float(self.integrated_xgyro as f64)),
+            ("integrated_ygyro", MavValue::Float(self.integrated_ygyro as f64)),
+            ("integrated_zgyro", MavValue::Float(self.integrated_zgyro as f64)),
+            ("time_delta_distance_us", MavValue::UInt(self.time_delta_distance_us as u64)),
+            ("distance", MavValue::Float(self.distance as f64)),
+            ("temperature", MavValue::Int(self.temperature as i64)),
+            ("sensor_id", MavValue::UInt(self.sensor_id as u64)),
+            ("quality", MavValue::UInt(self.quality as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HIL_SENSOR_DATA {
     pub time_usec: u64,
@@ -3536,6 +4614,28 @@ impl Parsable for HIL_SENSOR_DATA {
     }
 }
 
+impl HIL_SENSOR_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("xacc", MavValue::Float(self.xacc as f64)),
+            ("yacc", MavValue::Float(self.yacc as f64)),
+            ("zacc", MavValue::Float(self.zacc as f64)),
+            ("xgyro", MavValue::Float(self.xgyro as f64)),
+            ("ygyro", MavValue::Float(self.ygyro as f64)),
+            ("zgyro", MavValue::Float(self.zgyro as f64)),
+            ("xmag", MavValue::Float(self.xmag as f64)),
+            ("ymag", MavValue::Float(self.ymag as f64)),
+            ("zmag", MavValue::Float(self.zmag as f64)),
+            ("abs_pressure", MavValue::Float(self.abs_pressure as f64)),
+            ("diff_pressure", MavValue::Float(self.diff_pressure as f64)),
+            ("pressure_alt", MavValue::Float(self.pressure_alt as f64)),
+            ("temperature", MavValue::Float(self.temperature as f64)),
+            ("fields_updated", MavValue::UInt(self.fields_updated as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SIM_STATE_DATA {
     pub q1: f32,
@@ -3615,6 +4715,34 @@ impl Parsable for SIM_STATE_DATA {
     }
 }
 
+impl SIM_STATE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("q1", MavValue::Float(self.q1 as f64)),
+            ("q2", MavValue::Float(self.q2 as f64)),
+            ("q3", MavValue::Float(self.q3 as f64)),
+            ("q4", MavValue::Float(self.q4 as f64)),
+            ("roll", MavValue::Float(self.roll as f64)),
+            ("pitch", MavValue::Float(self.pitch as f64)),
+            ("yaw", MavValue::Float(self.yaw as f64)),
+            ("xacc", MavValue::Float(self.xacc as f64)),
+            ("yacc", MavValue::Float(self.yacc as f64)),
+            ("zacc", MavValue::Float(self.zacc as f64)),
+            ("xgyro", MavValue::Float(self.xgyro as f64)),
+            ("ygyro", MavValue::Float(self.ygyro as f64)),
+            ("zgyro", MavValue::Float(self.zgyro as f64)),
+            ("lat", MavValue::Float(self.lat as f64)),
+            ("lon", MavValue::Float(self.lon as f64)),
+            ("alt", MavValue::Float(self.alt as f64)),
+            ("std_dev_horz", MavValue::Float(self.std_dev_horz as f64)),
+            ("std_dev_vert", MavValue::Float(self.std_dev_vert as f64)),
+            ("vn", MavValue::Float(self.vn as f64)),
+            ("ve", MavValue::Float(self.ve as f64)),
+            ("vd", MavValue::Float(self.vd as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RADIO_STATUS_DATA {
     pub rxerrors: u16,
@@ -3652,6 +4780,20 @@ impl Parsable for RADIO_STATUS_DATA {
     }
 }
 
+impl RADIO_STATUS_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("rxerrors", MavValue::UInt(self.rxerrors as u64)),
+            ("fixed", MavValue::UInt(self.fixed as u64)),
+            ("rssi", MavValue::UInt(self.rssi as u64)),
+            ("remrssi", MavValue::UInt(self.remrssi as u64)),
+            ("txbuf", MavValue::UInt(self.txbuf as u64)),
+            ("noise", MavValue::UInt(self.noise as u64)),
+            ("remnoise", MavValue::UInt(self.remnoise as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct FILE_TRANSFER_PROTOCOL_DATA {
     pub target_network: u8,
@@ -4182,6 +5324,17 @@ impl Parsable for FILE_TRANSFER_PROTOCOL_DATA {
     }
 }
 
+impl FILE_TRANSFER_PROTOCOL_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("target_network", MavValue::UInt(self.target_network as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("payload", MavValue::Array(self.payload.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TIMESYNC_DATA {
     pub tc1: i64,
@@ -4204,6 +5357,15 @@ impl Parsable for TIMESYNC_DATA {
     }
 }
 
+impl TIMESYNC_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("tc1", MavValue::Int(self.tc1)),
+            ("ts1", MavValue::Int(self.ts1)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CAMERA_TRIGGER_DATA {
     pub time_usec: u64,
@@ -4226,6 +5388,15 @@ impl Parsable for CAMERA_TRIGGER_DATA {
     }
 }
 
+impl CAMERA_TRIGGER_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("seq", MavValue::UInt(self.seq as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HIL_GPS_DATA {
     pub time_usec: u64,
@@ -4281,6 +5452,26 @@ impl Parsable for HIL_GPS_DATA {
     }
 }
 
+impl HIL_GPS_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+            ("alt", MavValue::Int(self.alt as i64)),
+            ("eph", MavValue::UInt(self.eph as u64)),
+            ("epv", MavValue::UInt(self.epv as u64)),
+            ("vel", MavValue::UInt(self.vel as u64)),
+            ("vn", MavValue::Int(self.vn as i64)),
+            ("ve", MavValue::Int(self.ve as i64)),
+            ("vd", MavValue::Int(self.vd as i64)),
+            ("cog", MavValue::UInt(self.cog as u64)),
+            ("fix_type", MavValue::UInt(self.fix_type as u64)),
+            ("satellites_visible", MavValue::UInt(self.satellites_visible as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HIL_OPTICAL_FLOW_DATA {
     pub time_usec: u64,
@@ -4333,6 +5524,25 @@ impl Parsable for HIL_OPTICAL_FLOW_DATA {
     }
 }
 
+impl HIL_OPTICAL_FLOW_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("integration_time_us", MavValue::UInt(self.integration_time_us as u64)),
+            ("integrated_x", MavValue::Float(self.integrated_x as f64)),
+            ("integrated_y", MavValue::Float(self.integrated_y as f64)),
+            ("integrated_xgyro", MavValue::Float(self.integrated_xgyro as f64)),
+            ("integrated_ygyro", MavValue::Float(self.integrated_ygyro as f64)),
+            ("integrated_zgyro", MavValue::Float(self.integrated_zgyro as f64)),
+            ("time_delta_distance_us", MavValue::UInt(self.time_delta_distance_us as u64)),
+            ("distance", MavValue::Float(self.distance as f64)),
+            ("temperature", MavValue::Int(self.temperature as i64)),
+            ("sensor_id", MavValue::UInt(self.sensor_id as u64)),
+            ("quality", MavValue::UInt(self.quality as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HIL_STATE_QUATERNION_DATA {
     pub time_usec: u64,
@@ -4405,6 +5615,29 @@ impl Parsable for HIL_STATE_QUATERNION_DATA {
     }
 }
 
+impl HIL_STATE_QUATERNION_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("attitude_quaternion", MavValue::Array(self.attitude_quaternion.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("rollspeed", MavValue::Float(self.rollspeed as f64)),
+            ("pitchspeed", MavValue::Float(self.pitchspeed as f64)),
+            ("yawspeed", MavValue::Float(self.yawspeed as f64)),
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+            ("alt", MavValue::Int(self.alt as i64)),
+            ("vx", MavValue::Int(self.vx as i64)),
+            ("vy", MavValue::Int(self.vy as i64)),
+            ("vz", MavValue::Int(self.vz as i64)),
+            ("ind_airspeed", MavValue::UInt(self.ind_airspeed as u64)),
+            ("true_airspeed", MavValue::UInt(self.true_airspeed as u64)),
+            ("xacc", MavValue::Int(self.xacc as i64)),
+            ("yacc", MavValue::Int(self.yacc as i64)),
+            ("zacc", MavValue::Int(self.zacc as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SCALED_IMU2_DATA {
     pub time_boot_ms: u32,
@@ -4451,6 +5684,23 @@ impl Parsable for SCALED_IMU2_DATA {
     }
 }
 
+impl SCALED_IMU2_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("xacc", MavValue::Int(self.xacc as i64)),
+            ("yacc", MavValue::Int(self.yacc as i64)),
+            ("zacc", MavValue::Int(self.zacc as i64)),
+            ("xgyro", MavValue::Int(self.xgyro as i64)),
+            ("ygyro", MavValue::Int(self.ygyro as i64)),
+            ("zgyro", MavValue::Int(self.zgyro as i64)),
+            ("xmag", MavValue::Int(self.xmag as i64)),
+            ("ymag", MavValue::Int(self.ymag as i64)),
+            ("zmag", MavValue::Int(self.zmag as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LOG_REQUEST_LIST_DATA {
     pub start: u16,
@@ -4479,6 +5729,17 @@ impl Parsable for LOG_REQUEST_LIST_DATA {
     }
 }
 
+impl LOG_REQUEST_LIST_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("start", MavValue::UInt(self.start as u64)),
+            ("end", MavValue::UInt(self.end as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LOG_ENTRY_DATA {
     pub time_utc: u32,
@@ -4510,6 +5771,18 @@ impl Parsable for LOG_ENTRY_DATA {
     }
 }
 
+impl LOG_ENTRY_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_utc", MavValue::UInt(self.time_utc as u64)),
+            ("size", MavValue::UInt(self.size as u64)),
+            ("id", MavValue::UInt(self.id as u64)),
+            ("num_logs", MavValue::UInt(self.num_logs as u64)),
+            ("last_log_num", MavValue::UInt(self.last_log_num as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LOG_REQUEST_DATA_DATA {
     pub ofs: u32,
@@ -4541,6 +5814,18 @@ impl Parsable for LOG_REQUEST_DATA_DATA {
     }
 }
 
+impl LOG_REQUEST_DATA_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("ofs", MavValue::UInt(self.ofs as u64)),
+            ("count", MavValue::UInt(self.count as u64)),
+            ("id", MavValue::UInt(self.id as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LOG_DATA_DATA {
     pub ofs: u32,
@@ -4749,6 +6034,17 @@ impl Parsable for LOG_DATA_DATA {
     }
 }
 
+impl LOG_DATA_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("ofs", MavValue::UInt(self.ofs as u64)),
+            ("id", MavValue::UInt(self.id as u64)),
+            ("count", MavValue::UInt(self.count as u64)),
+            ("data", MavValue::Array(self.data.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LOG_ERASE_DATA {
     pub target_system: u8,
@@ -4771,6 +6067,15 @@ impl Parsable for LOG_ERASE_DATA {
     }
 }
 
+impl LOG_ERASE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LOG_REQUEST_END_DATA {
     pub target_system: u8,
@@ -4793,6 +6098,15 @@ impl Parsable for LOG_REQUEST_END_DATA {
     }
 }
 
+impl LOG_REQUEST_END_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GPS_INJECT_DATA_DATA {
     pub target_system: u8,
@@ -5041,6 +6355,17 @@ impl Parsable for GPS_INJECT_DATA_DATA {
     }
 }
 
+impl GPS_INJECT_DATA_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("len", MavValue::UInt(self.len as u64)),
+            ("data", MavValue::Array(self.data.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GPS2_RAW_DATA {
     pub time_usec: u64,
@@ -5093,6 +6418,25 @@ impl Parsable for GPS2_RAW_DATA {
     }
 }
 
+impl GPS2_RAW_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+            ("alt", MavValue::Int(self.alt as i64)),
+            ("dgps_age", MavValue::UInt(self.dgps_age as u64)),
+            ("eph", MavValue::UInt(self.eph as u64)),
+            ("epv", MavValue::UInt(self.epv as u64)),
+            ("vel", MavValue::UInt(self.vel as u64)),
+            ("cog", MavValue::UInt(self.cog as u64)),
+            ("fix_type", MavValue::UInt(self.fix_type as u64)),
+            ("satellites_visible", MavValue::UInt(self.satellites_visible as u64)),
+            ("dgps_numch", MavValue::UInt(self.dgps_numch as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct POWER_STATUS_DATA {
     pub Vcc: u16,
@@ -5118,6 +6462,16 @@ impl Parsable for POWER_STATUS_DATA {
     }
 }
 
+impl POWER_STATUS_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("Vcc", MavValue::UInt(self.Vcc as u64)),
+            ("Vservo", MavValue::UInt(self.Vservo as u64)),
+            ("flags", MavValue::UInt(self.flags as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SERIAL_CONTROL_DATA {
     pub baudrate: u32,
@@ -5292,6 +6646,19 @@ impl Parsable for SERIAL_CONTROL_DATA {
     }
 }
 
+impl SERIAL_CONTROL_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("baudrate", MavValue::UInt(self.baudrate as u64)),
+            ("timeout", MavValue::UInt(self.timeout as u64)),
+            ("device", MavValue::UInt(self.device as u64)),
+            ("flags", MavValue::UInt(self.flags as u64)),
+            ("count", MavValue::UInt(self.count as u64)),
+            ("data", MavValue::Array(self.data.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GPS_RTK_DATA {
     pub time_last_baseline_ms: u32,
@@ -5347,6 +6714,26 @@ impl Parsable for GPS_RTK_DATA {
     }
 }
 
+impl GPS_RTK_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_last_baseline_ms", MavValue::UInt(self.time_last_baseline_ms as u64)),
+            ("tow", MavValue::UInt(self.tow as u64)),
+            ("baseline_a_mm", MavValue::Int(self.baseline_a_mm as i64)),
+            ("baseline_b_mm", MavValue::Int(self.baseline_b_mm as i64)),
+            ("baseline_c_mm", MavValue::Int(self.baseline_c_mm as i64)),
+            ("accuracy", MavValue::UInt(self.accuracy as u64)),
+            ("iar_num_hypotheses", MavValue::Int(self.iar_num_hypotheses as i64)),
+            ("wn", MavValue::UInt(self.wn as u64)),
+            ("rtk_receiver_id", MavValue::UInt(self.rtk_receiver_id as u64)),
+            ("rtk_health", MavValue::UInt(self.rtk_health as u64)),
+            ("rtk_rate", MavValue::UInt(self.rtk_rate as u64)),
+            ("nsats", MavValue::UInt(self.nsats as u64)),
+            ("baseline_coords_type", MavValue::UInt(self.baseline_coords_type as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GPS2_RTK_DATA {
     pub time_last_baseline_ms: u32,
@@ -5402,6 +6789,26 @@ impl Parsable for GPS2_RTK_DATA {
     }
 }
 
+impl GPS2_RTK_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_last_baseline_ms", MavValue::UInt(self.time_last_baseline_ms as u64)),
+            ("tow", MavValue::UInt(self.tow as u64)),
+            ("baseline_a_mm", MavValue::Int(self.baseline_a_mm as i64)),
+            ("baseline_b_mm", MavValue::Int(self.baseline_b_mm as i64)),
+            ("baseline_c_mm", MavValue::Int(self.baseline_c_mm as i64)),
+            ("accuracy", MavValue::UInt(self.accuracy as u64)),
+            ("iar_num_hypotheses", MavValue::Int(self.iar_num_hypotheses as i64)),
+            ("wn", MavValue::UInt(self.wn as u64)),
+            ("rtk_receiver_id", MavValue::UInt(self.rtk_receiver_id as u64)),
+            ("rtk_health", MavValue::UInt(self.rtk_health as u64)),
+            ("rtk_rate", MavValue::UInt(self.rtk_rate as u64)),
+            ("nsats", MavValue::UInt(self.nsats as u64)),
+            ("baseline_coords_type", MavValue::UInt(self.baseline_coords_type as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SCALED_IMU3_DATA {
     pub time_boot_ms: u32,
@@ -5448,6 +6855,23 @@ impl Parsable for SCALED_IMU3_DATA {
     }
 }
 
+impl SCALED_IMU3_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("xacc", MavValue::Int(self.xacc as i64)),
+            ("yacc", MavValue::Int(self.yacc as i64)),
+            ("zacc", MavValue::Int(self.zacc as i64)),
+            ("xgyro", MavValue::Int(self.xgyro as i64)),
+            ("ygyro", MavValue::Int(self.ygyro as i64)),
+            ("zgyro", MavValue::Int(self.zgyro as i64)),
+            ("xmag", MavValue::Int(self.xmag as i64)),
+            ("ymag", MavValue::Int(self.ymag as i64)),
+            ("zmag", MavValue::Int(self.zmag as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DATA_TRANSMISSION_HANDSHAKE_DATA {
     pub size: u32,
@@ -5485,6 +6909,20 @@ impl Parsable for DATA_TRANSMISSION_HANDSHAKE_DATA {
     }
 }
 
+impl DATA_TRANSMISSION_HANDSHAKE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("size", MavValue::UInt(self.size as u64)),
+            ("width", MavValue::UInt(self.width as u64)),
+            ("height", MavValue::UInt(self.height as u64)),
+            ("packets", MavValue::UInt(self.packets as u64)),
+            ("type", MavValue::UInt(self.mavtype as u64)),
+            ("payload", MavValue::UInt(self.payload as u64)),
+            ("jpg_quality", MavValue::UInt(self.jpg_quality as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ENCAPSULATED_DATA_DATA {
     pub seqnr: u16,
@@ -6013,6 +7451,15 @@ impl Parsable for ENCAPSULATED_DATA_DATA {
     }
 }
 
+impl ENCAPSULATED_DATA_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("seqnr", MavValue::UInt(self.seqnr as u64)),
+            ("data", MavValue::Array(self.data.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DISTANCE_SENSOR_DATA {
     pub time_boot_ms: u32,
@@ -6053,6 +7500,21 @@ impl Parsable for DISTANCE_SENSOR_DATA {
     }
 }
 
+impl DISTANCE_SENSOR_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("min_distance", MavValue::UInt(self.min_distance as u64)),
+            ("max_distance", MavValue::UInt(self.max_distance as u64)),
+            ("current_distance", MavValue::UInt(self.current_distance as u64)),
+            ("type", MavValue::UInt(self.mavtype as u64)),
+            ("id", MavValue::UInt(self.id as u64)),
+            ("orientation", MavValue::UInt(self.orientation as u64)),
+            ("covariance", MavValue::UInt(self.covariance as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TERRAIN_REQUEST_DATA {
     pub mask: u64,
@@ -6081,6 +7543,17 @@ impl Parsable for TERRAIN_REQUEST_DATA {
     }
 }
 
+impl TERRAIN_REQUEST_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("mask", MavValue::UInt(self.mask)),
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+            ("grid_spacing", MavValue::UInt(self.grid_spacing as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TERRAIN_DATA_DATA {
     pub lat: i32,
@@ -6144,6 +7617,18 @@ impl Parsable for TERRAIN_DATA_DATA {
     }
 }
 
+impl TERRAIN_DATA_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+            ("grid_spacing", MavValue::UInt(self.grid_spacing as u64)),
+            ("data", MavValue::Array(self.data.iter().map(|&x| MavValue::Int(x as i64)).collect())),
+            ("gridbit", MavValue::UInt(self.gridbit as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TERRAIN_CHECK_DATA {
     pub lat: i32,
@@ -6166,6 +7651,15 @@ impl Parsable for TERRAIN_CHECK_DATA {
     }
 }
 
+impl TERRAIN_CHECK_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TERRAIN_REPORT_DATA {
     pub lat: i32,
@@ -6203,6 +7697,20 @@ impl Parsable for TERRAIN_REPORT_DATA {
     }
 }
 
+impl TERRAIN_REPORT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+            ("terrain_height", MavValue::Float(self.terrain_height as f64)),
+            ("current_height", MavValue::Float(self.current_height as f64)),
+            ("spacing", MavValue::UInt(self.spacing as u64)),
+            ("pending", MavValue::UInt(self.pending as u64)),
+            ("loaded", MavValue::UInt(self.loaded as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SCALED_PRESSURE2_DATA {
     pub time_boot_ms: u32,
@@ -6231,6 +7739,17 @@ impl Parsable for SCALED_PRESSURE2_DATA {
     }
 }
 
+impl SCALED_PRESSURE2_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("press_abs", MavValue::Float(self.press_abs as f64)),
+            ("press_diff", MavValue::Float(self.press_diff as f64)),
+            ("temperature", MavValue::Int(self.temperature as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ATT_POS_MOCAP_DATA {
     pub time_usec: u64,
@@ -6270,6 +7789,18 @@ impl Parsable for ATT_POS_MOCAP_DATA {
     }
 }
 
+impl ATT_POS_MOCAP_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("q", MavValue::Array(self.q.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SET_ACTUATOR_CONTROL_TARGET_DATA {
     pub time_usec: u64,
@@ -6317,6 +7848,18 @@ impl Parsable for SET_ACTUATOR_CONTROL_TARGET_DATA {
     }
 }
 
+impl SET_ACTUATOR_CONTROL_TARGET_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("controls", MavValue::Array(self.controls.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("group_mlx", MavValue::UInt(self.group_mlx as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ACTUATOR_CONTROL_TARGET_DATA {
     pub time_usec: u64,
@@ -6358,6 +7901,16 @@ impl Parsable for ACTUATOR_CONTROL_TARGET_DATA {
     }
 }
 
+impl ACTUATOR_CONTROL_TARGET_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("controls", MavValue::Array(self.controls.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("group_mlx", MavValue::UInt(self.group_mlx as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ALTITUDE_DATA {
     pub time_usec: u64,
@@ -6395,6 +7948,20 @@ impl Parsable for ALTITUDE_DATA {
     }
 }
 
+impl ALTITUDE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("altitude_monotonic", MavValue::Float(self.altitude_monotonic as f64)),
+            ("altitude_amsl", MavValue::Float(self.altitude_amsl as f64)),
+            ("altitude_local", MavValue::Float(self.altitude_local as f64)),
+            ("altitude_relative", MavValue::Float(self.altitude_relative as f64)),
+            ("altitude_terrain", MavValue::Float(self.altitude_terrain as f64)),
+            ("bottom_clearance", MavValue::Float(self.bottom_clearance as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RESOURCE_REQUEST_DATA {
     pub request_id: u8,
@@ -6906,6 +8473,18 @@ impl Parsable for RESOURCE_REQUEST_DATA {
     }
 }
 
+impl RESOURCE_REQUEST_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("request_id", MavValue::UInt(self.request_id as u64)),
+            ("uri_type", MavValue::UInt(self.uri_type as u64)),
+            ("uri", MavValue::Array(self.uri.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+            ("transfer_type", MavValue::UInt(self.transfer_type as u64)),
+            ("storage", MavValue::Array(self.storage.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SCALED_PRESSURE3_DATA {
     pub time_boot_ms: u32,
@@ -6934,6 +8513,17 @@ impl Parsable for SCALED_PRESSURE3_DATA {
     }
 }
 
+impl SCALED_PRESSURE3_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("press_abs", MavValue::Float(self.press_abs as f64)),
+            ("press_diff", MavValue::Float(self.press_diff as f64)),
+            ("temperature", MavValue::Int(self.temperature as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CONTROL_SYSTEM_STATE_DATA {
     pub time_usec: u64,
@@ -7021,6 +8611,30 @@ impl Parsable for CONTROL_SYSTEM_STATE_DATA {
     }
 }
 
+impl CONTROL_SYSTEM_STATE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("x_acc", MavValue::Float(self.x_acc as f64)),
+            ("y_acc", MavValue::Float(self.y_acc as f64)),
+            ("z_acc", MavValue::Float(self.z_acc as f64)),
+            ("x_vel", MavValue::Float(self.x_vel as f64)),
+            ("y_vel", MavValue::Float(self.y_vel as f64)),
+            ("z_vel", MavValue::Float(self.z_vel as f64)),
+            ("x_pos", MavValue::Float(self.x_pos as f64)),
+            ("y_pos", MavValue::Float(self.y_pos as f64)),
+            ("z_pos", MavValue::Float(self.z_pos as f64)),
+            ("airspeed", MavValue::Float(self.airspeed as f64)),
+            ("vel_variance", MavValue::Array(self.vel_variance.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("pos_variance", MavValue::Array(self.pos_variance.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("q", MavValue::Array(self.q.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("roll_rate", MavValue::Float(self.roll_rate as f64)),
+            ("pitch_rate", MavValue::Float(self.pitch_rate as f64)),
+            ("yaw_rate", MavValue::Float(self.yaw_rate as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct BATTERY_STATUS_DATA {
     pub current_consumed: i32,
@@ -7084,6 +8698,22 @@ impl Parsable for BATTERY_STATUS_DATA {
     }
 }
 
+impl BATTERY_STATUS_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("current_consumed", MavValue::Int(self.current_consumed as i64)),
+            ("energy_consumed", MavValue::Int(self.energy_consumed as i64)),
+            ("temperature", MavValue::Int(self.temperature as i64)),
+            ("voltages", MavValue::Array(self.voltages.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+            ("current_battery", MavValue::Int(self.current_battery as i64)),
+            ("id", MavValue::UInt(self.id as u64)),
+            ("battery_function", MavValue::UInt(self.battery_function as u64)),
+            ("type", MavValue::UInt(self.mavtype as u64)),
+            ("battery_remaining", MavValue::Int(self.battery_remaining as i64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AUTOPILOT_VERSION_DATA {
     pub capabilities: u64,
@@ -7181,6 +8811,24 @@ impl Parsable for AUTOPILOT_VERSION_DATA {
     }
 }
 
+impl AUTOPILOT_VERSION_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("capabilities", MavValue::UInt(self.capabilities)),
+            ("uid", MavValue::UInt(self.uid)),
+            ("flight_sw_version", MavValue::UInt(self.flight_sw_version as u64)),
+            ("middleware_sw_version", MavValue::UInt(self.middleware_sw_version as u64)),
+            ("os_sw_version", MavValue::UInt(self.os_sw_version as u64)),
+            ("board_version", MavValue::UInt(self.board_version as u64)),
+            ("vendor_id", MavValue::UInt(self.vendor_id as u64)),
+            ("product_id", MavValue::UInt(self.product_id as u64)),
+            ("flight_custom_version", MavValue::Array(self.flight_custom_version.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+            ("middleware_custom_version", MavValue::Array(self.middleware_custom_version.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+            ("os_custom_version", MavValue::Array(self.os_custom_version.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LANDING_TARGET_DATA {
     pub time_usec: u64,
@@ -7221,6 +8869,201 @@ impl Parsable for LANDING_TARGET_DATA {
     }
 }
 
+impl LANDING_TARGET_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("angle_x", MavValue::Float(self.angle_x as f64)),
+            ("angle_y", MavValue::Float(self.angle_y as f64)),
+            ("distance", MavValue::Float(self.distance as f64)),
+            ("size_x", MavValue::Float(self.size_x as f64)),
+            ("size_y", MavValue::Float(self.size_y as f64)),
+            ("target_num", MavValue::UInt(self.target_num as u64)),
+            ("frame", MavValue::UInt(self.frame as u64)),
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FENCE_POINT_DATA {
+    pub lat: f32,
+    pub lng: f32,
+    pub target_system: u8,
+    pub target_component: u8,
+    pub idx: u8,
+    pub count: u8,
+}
+
+impl Parsable for FENCE_POINT_DATA {
+    fn parse(payload: &[u8]) -> FENCE_POINT_DATA {
+        let mut cur = Cursor::new(payload);
+        FENCE_POINT_DATA {
+            lat: cur.read_f32::<LittleEndian>().unwrap(),
+            lng: cur.read_f32::<LittleEndian>().unwrap(),
+            target_system: cur.read_u8().unwrap(),
+            target_component: cur.read_u8().unwrap(),
+            idx: cur.read_u8().unwrap(),
+            count: cur.read_u8().unwrap(),
+        }
+    }
+    fn serialize(&self) -> Vec<u8> {
+        let mut wtr = vec![];
+        wtr.write_f32::<LittleEndian>(self.lat).unwrap();
+        wtr.write_f32::<LittleEndian>(self.lng).unwrap();
+        wtr.write_u8(self.target_system).unwrap();
+        wtr.write_u8(self.target_component).unwrap();
+        wtr.write_u8(self.idx).unwrap();
+        wtr.write_u8(self.count).unwrap();
+        wtr
+    }
+}
+
+impl FENCE_POINT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("lat", MavValue::Float(self.lat as f64)),
+            ("lng", MavValue::Float(self.lng as f64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("idx", MavValue::UInt(self.idx as u64)),
+            ("count", MavValue::UInt(self.count as u64)),
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FENCE_FETCH_POINT_DATA {
+    pub target_system: u8,
+    pub target_component: u8,
+    pub idx: u8,
+}
+
+impl Parsable for FENCE_FETCH_POINT_DATA {
+    fn parse(payload: &[u8]) -> FENCE_FETCH_POINT_DATA {
+        let mut cur = Cursor::new(payload);
+        FENCE_FETCH_POINT_DATA {
+            target_system: cur.read_u8().unwrap(),
+            target_component: cur.read_u8().unwrap(),
+            idx: cur.read_u8().unwrap(),
+        }
+    }
+    fn serialize(&self) -> Vec<u8> {
+        let mut wtr = vec![];
+        wtr.write_u8(self.target_system).unwrap();
+        wtr.write_u8(self.target_component).unwrap();
+        wtr.write_u8(self.idx).unwrap();
+        wtr
+    }
+}
+
+impl FENCE_FETCH_POINT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("idx", MavValue::UInt(self.idx as u64)),
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RALLY_POINT_DATA {
+    pub lat: i32,
+    pub lng: i32,
+    pub alt: i16,
+    pub break_alt: i16,
+    pub land_dir: u16,
+    pub target_system: u8,
+    pub target_component: u8,
+    pub idx: u8,
+    pub count: u8,
+    pub flags: u8,
+}
+
+impl Parsable for RALLY_POINT_DATA {
+    fn parse(payload: &[u8]) -> RALLY_POINT_DATA {
+        let mut cur = Cursor::new(payload);
+        RALLY_POINT_DATA {
+            lat: cur.read_i32::<LittleEndian>().unwrap(),
+            lng: cur.read_i32::<LittleEndian>().unwrap(),
+            alt: cur.read_i16::<LittleEndian>().unwrap(),
+            break_alt: cur.read_i16::<LittleEndian>().unwrap(),
+            land_dir: cur.read_u16::<LittleEndian>().unwrap(),
+            target_system: cur.read_u8().unwrap(),
+            target_component: cur.read_u8().unwrap(),
+            idx: cur.read_u8().unwrap(),
+            count: cur.read_u8().unwrap(),
+            flags: cur.read_u8().unwrap(),
+        }
+    }
+    fn serialize(&self) -> Vec<u8> {
+        let mut wtr = vec![];
+        wtr.write_i32::<LittleEndian>(self.lat).unwrap();
+        wtr.write_i32::<LittleEndian>(self.lng).unwrap();
+        wtr.write_i16::<LittleEndian>(self.alt).unwrap();
+        wtr.write_i16::<LittleEndian>(self.break_alt).unwrap();
+        wtr.write_u16::<LittleEndian>(self.land_dir).unwrap();
+        wtr.write_u8(self.target_system).unwrap();
+        wtr.write_u8(self.target_component).unwrap();
+        wtr.write_u8(self.idx).unwrap();
+        wtr.write_u8(self.count).unwrap();
+        wtr.write_u8(self.flags).unwrap();
+        wtr
+    }
+}
+
+impl RALLY_POINT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lng", MavValue::Int(self.lng as i64)),
+            ("alt", MavValue::Int(self.alt as i64)),
+            ("break_alt", MavValue::Int(self.break_alt as i64)),
+            ("land_dir", MavValue::UInt(self.land_dir as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("idx", MavValue::UInt(self.idx as u64)),
+            ("count", MavValue::UInt(self.count as u64)),
+            ("flags", MavValue::UInt(self.flags as u64)),
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RALLY_FETCH_POINT_DATA {
+    pub target_system: u8,
+    pub target_component: u8,
+    pub idx: u8,
+}
+
+impl Parsable for RALLY_FETCH_POINT_DATA {
+    fn parse(payload: &[u8]) -> RALLY_FETCH_POINT_DATA {
+        let mut cur = Cursor::new(payload);
+        RALLY_FETCH_POINT_DATA {
+            target_system: cur.read_u8().unwrap(),
+            target_component: cur.read_u8().unwrap(),
+            idx: cur.read_u8().unwrap(),
+        }
+    }
+    fn serialize(&self) -> Vec<u8> {
+        let mut wtr = vec![];
+        wtr.write_u8(self.target_system).unwrap();
+        wtr.write_u8(self.target_component).unwrap();
+        wtr.write_u8(self.idx).unwrap();
+        wtr
+    }
+}
+
+impl RALLY_FETCH_POINT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("idx", MavValue::UInt(self.idx as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct VIBRATION_DATA {
     pub time_usec: u64,
@@ -7258,6 +9101,20 @@ impl Parsable for VIBRATION_DATA {
     }
 }
 
+impl VIBRATION_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("vibration_x", MavValue::Float(self.vibration_x as f64)),
+            ("vibration_y", MavValue::Float(self.vibration_y as f64)),
+            ("vibration_z", MavValue::Float(self.vibration_z as f64)),
+            ("clipping_0", MavValue::UInt(self.clipping_0 as u64)),
+            ("clipping_1", MavValue::UInt(self.clipping_1 as u64)),
+            ("clipping_2", MavValue::UInt(self.clipping_2 as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HOME_POSITION_DATA {
     pub latitude: i32,
@@ -7312,6 +9169,23 @@ impl Parsable for HOME_POSITION_DATA {
     }
 }
 
+impl HOME_POSITION_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("latitude", MavValue::Int(self.latitude as i64)),
+            ("longitude", MavValue::Int(self.longitude as i64)),
+            ("altitude", MavValue::Int(self.altitude as i64)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("q", MavValue::Array(self.q.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("approach_x", MavValue::Float(self.approach_x as f64)),
+            ("approach_y", MavValue::Float(self.approach_y as f64)),
+            ("approach_z", MavValue::Float(self.approach_z as f64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SET_HOME_POSITION_DATA {
     pub latitude: i32,
@@ -7369,6 +9243,24 @@ impl Parsable for SET_HOME_POSITION_DATA {
     }
 }
 
+impl SET_HOME_POSITION_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("latitude", MavValue::Int(self.latitude as i64)),
+            ("longitude", MavValue::Int(self.longitude as i64)),
+            ("altitude", MavValue::Int(self.altitude as i64)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("q", MavValue::Array(self.q.iter().map(|&x| MavValue::Float(x as f64)).collect())),
+            ("approach_x", MavValue::Float(self.approach_x as f64)),
+            ("approach_y", MavValue::Float(self.approach_y as f64)),
+            ("approach_z", MavValue::Float(self.approach_z as f64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MESSAGE_INTERVAL_DATA {
     pub interval_us: i32,
@@ -7391,6 +9283,15 @@ impl Parsable for MESSAGE_INTERVAL_DATA {
     }
 }
 
+impl MESSAGE_INTERVAL_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("interval_us", MavValue::Int(self.interval_us as i64)),
+            ("message_id", MavValue::UInt(self.message_id as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct EXTENDED_SYS_STATE_DATA {
     pub vtol_state: u8,
@@ -7413,6 +9314,15 @@ impl Parsable for EXTENDED_SYS_STATE_DATA {
     }
 }
 
+impl EXTENDED_SYS_STATE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("vtol_state", MavValue::UInt(self.vtol_state as u64)),
+            ("landed_state", MavValue::UInt(self.landed_state as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ADSB_VEHICLE_DATA {
     pub ICAO_address: u32,
@@ -7486,6 +9396,26 @@ impl Parsable for ADSB_VEHICLE_DATA {
     }
 }
 
+impl ADSB_VEHICLE_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("ICAO_address", MavValue::UInt(self.ICAO_address as u64)),
+            ("lat", MavValue::Int(self.lat as i64)),
+            ("lon", MavValue::Int(self.lon as i64)),
+            ("altitude", MavValue::Float(self.altitude as f64)),
+            ("hor_velocity", MavValue::Float(self.hor_velocity as f64)),
+            ("ver_velocity", MavValue::Float(self.ver_velocity as f64)),
+            ("heading", MavValue::UInt(self.heading as u64)),
+            ("flags", MavValue::UInt(self.flags as u64)),
+            ("squawk", MavValue::UInt(self.squawk as u64)),
+            ("altitude_type", MavValue::UInt(self.altitude_type as u64)),
+            ("callsign", MavValue::Text(text(&self.callsign))),
+            ("emitter_type", MavValue::UInt(self.emitter_type as u64)),
+            ("tslc", MavValue::UInt(self.tslc as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct V2_EXTENSION_DATA {
     pub message_type: u16,
@@ -8015,6 +9945,18 @@ impl Parsable for V2_EXTENSION_DATA {
     }
 }
 
+impl V2_EXTENSION_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("message_type", MavValue::UInt(self.message_type as u64)),
+            ("target_network", MavValue::UInt(self.target_network as u64)),
+            ("target_system", MavValue::UInt(self.target_system as u64)),
+            ("target_component", MavValue::UInt(self.target_component as u64)),
+            ("payload", MavValue::Array(self.payload.iter().map(|&x| MavValue::UInt(x as u64)).collect())),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct MEMORY_VECT_DATA {
     pub address: u16,
@@ -8107,6 +10049,17 @@ impl Parsable for MEMORY_VECT_DATA {
     }
 }
 
+impl MEMORY_VECT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("address", MavValue::UInt(self.address as u64)),
+            ("ver", MavValue::UInt(self.ver as u64)),
+            ("type", MavValue::UInt(self.mavtype as u64)),
+            ("value", MavValue::Array(self.value.iter().map(|&x| MavValue::Int(x as i64)).collect())),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DEBUG_VECT_DATA {
     pub time_usec: u64,
@@ -8158,6 +10111,18 @@ impl Parsable for DEBUG_VECT_DATA {
     }
 }
 
+impl DEBUG_VECT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_usec", MavValue::UInt(self.time_usec)),
+            ("x", MavValue::Float(self.x as f64)),
+            ("y", MavValue::Float(self.y as f64)),
+            ("z", MavValue::Float(self.z as f64)),
+            ("name", MavValue::Text(text(&self.name))),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct NAMED_VALUE_FLOAT_DATA {
     pub time_boot_ms: u32,
@@ -8203,6 +10168,16 @@ impl Parsable for NAMED_VALUE_FLOAT_DATA {
     }
 }
 
+impl NAMED_VALUE_FLOAT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("value", MavValue::Float(self.value as f64)),
+            ("name", MavValue::Text(text(&self.name))),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct NAMED_VALUE_INT_DATA {
     pub time_boot_ms: u32,
@@ -8248,6 +10223,16 @@ impl Parsable for NAMED_VALUE_INT_DATA {
     }
 }
 
+impl NAMED_VALUE_INT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("value", MavValue::Int(self.value as i64)),
+            ("name", MavValue::Text(text(&self.name))),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct STATUSTEXT_DATA {
     pub severity: u8,
@@ -8370,6 +10355,15 @@ impl Parsable for STATUSTEXT_DATA {
     }
 }
 
+impl STATUSTEXT_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("severity", MavValue::UInt(self.severity as u64)),
+            ("text", MavValue::Text(text(&self.text))),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DEBUG_DATA {
     pub time_boot_ms: u32,
@@ -8395,6 +10389,16 @@ impl Parsable for DEBUG_DATA {
     }
 }
 
+impl DEBUG_DATA {
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        vec![
+            ("time_boot_ms", MavValue::UInt(self.time_boot_ms as u64)),
+            ("value", MavValue::Float(self.value as f64)),
+            ("ind", MavValue::UInt(self.ind as u64)),
+        ]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum DkMessage {
     HEARTBEAT(HEARTBEAT_DATA),
@@ -8511,6 +10515,10 @@ pub enum DkMessage {
     BATTERY_STATUS(BATTERY_STATUS_DATA),
     AUTOPILOT_VERSION(AUTOPILOT_VERSION_DATA),
     LANDING_TARGET(LANDING_TARGET_DATA),
+    FENCE_POINT(FENCE_POINT_DATA),
+    FENCE_FETCH_POINT(FENCE_FETCH_POINT_DATA),
+    RALLY_POINT(RALLY_POINT_DATA),
+    RALLY_FETCH_POINT(RALLY_FETCH_POINT_DATA),
     VIBRATION(VIBRATION_DATA),
     HOME_POSITION(HOME_POSITION_DATA),
     SET_HOME_POSITION(SET_HOME_POSITION_DATA),
@@ -8653,6 +10661,10 @@ impl DkMessage {
             147 => Some(DkMessage::BATTERY_STATUS(BATTERY_STATUS_DATA::parse(payload))),
             148 => Some(DkMessage::AUTOPILOT_VERSION(AUTOPILOT_VERSION_DATA::parse(payload))),
             149 => Some(DkMessage::LANDING_TARGET(LANDING_TARGET_DATA::parse(payload))),
+            160 => Some(DkMessage::FENCE_POINT(FENCE_POINT_DATA::parse(payload))),
+            161 => Some(DkMessage::FENCE_FETCH_POINT(FENCE_FETCH_POINT_DATA::parse(payload))),
+            175 => Some(DkMessage::RALLY_POINT(RALLY_POINT_DATA::parse(payload))),
+            176 => Some(DkMessage::RALLY_FETCH_POINT(RALLY_FETCH_POINT_DATA::parse(payload))),
             241 => Some(DkMessage::VIBRATION(VIBRATION_DATA::parse(payload))),
             242 => Some(DkMessage::HOME_POSITION(HOME_POSITION_DATA::parse(payload))),
             243 => Some(DkMessage::SET_HOME_POSITION(SET_HOME_POSITION_DATA::parse(payload))),
@@ -8786,6 +10798,10 @@ impl DkMessage {
             &DkMessage::BATTERY_STATUS(..) => 147,
             &DkMessage::AUTOPILOT_VERSION(..) => 148,
             &DkMessage::LANDING_TARGET(..) => 149,
+            &DkMessage::FENCE_POINT(..) => 160,
+            &DkMessage::FENCE_FETCH_POINT(..) => 161,
+            &DkMessage::RALLY_POINT(..) => 175,
+            &DkMessage::RALLY_FETCH_POINT(..) => 176,
             &DkMessage::VIBRATION(..) => 241,
             &DkMessage::HOME_POSITION(..) => 242,
             &DkMessage::SET_HOME_POSITION(..) => 243,
@@ -8802,6 +10818,142 @@ impl DkMessage {
         }
     }
 
+    pub fn name(&self) -> &'static str {
+        match self {
+            &DkMessage::HEARTBEAT(..) => "HEARTBEAT",
+            &DkMessage::SYS_STATUS(..) => "SYS_STATUS",
+            &DkMessage::SYSTEM_TIME(..) => "SYSTEM_TIME",
+            &DkMessage::PING(..) => "PING",
+            &DkMessage::CHANGE_OPERATOR_CONTROL(..) => "CHANGE_OPERATOR_CONTROL",
+            &DkMessage::CHANGE_OPERATOR_CONTROL_ACK(..) => "CHANGE_OPERATOR_CONTROL_ACK",
+            &DkMessage::AUTH_KEY(..) => "AUTH_KEY",
+            &DkMessage::SET_MODE(..) => "SET_MODE",
+            &DkMessage::PARAM_REQUEST_READ(..) => "PARAM_REQUEST_READ",
+            &DkMessage::PARAM_REQUEST_LIST(..) => "PARAM_REQUEST_LIST",
+            &DkMessage::PARAM_VALUE(..) => "PARAM_VALUE",
+            &DkMessage::PARAM_SET(..) => "PARAM_SET",
+            &DkMessage::GPS_RAW_INT(..) => "GPS_RAW_INT",
+            &DkMessage::GPS_STATUS(..) => "GPS_STATUS",
+            &DkMessage::SCALED_IMU(..) => "SCALED_IMU",
+            &DkMessage::RAW_IMU(..) => "RAW_IMU",
+            &DkMessage::RAW_PRESSURE(..) => "RAW_PRESSURE",
+            &DkMessage::SCALED_PRESSURE(..) => "SCALED_PRESSURE",
+            &DkMessage::ATTITUDE(..) => "ATTITUDE",
+            &DkMessage::ATTITUDE_QUATERNION(..) => "ATTITUDE_QUATERNION",
+            &DkMessage::LOCAL_POSITION_NED(..) => "LOCAL_POSITION_NED",
+            &DkMessage::GLOBAL_POSITION_INT(..) => "GLOBAL_POSITION_INT",
+            &DkMessage::RC_CHANNELS_SCALED(..) => "RC_CHANNELS_SCALED",
+            &DkMessage::RC_CHANNELS_RAW(..) => "RC_CHANNELS_RAW",
+            &DkMessage::SERVO_OUTPUT_RAW(..) => "SERVO_OUTPUT_RAW",
+            &DkMessage::MISSION_REQUEST_PARTIAL_LIST(..) => "MISSION_REQUEST_PARTIAL_LIST",
+            &DkMessage::MISSION_WRITE_PARTIAL_LIST(..) => "MISSION_WRITE_PARTIAL_LIST",
+            &DkMessage::MISSION_ITEM(..) => "MISSION_ITEM",
+            &DkMessage::MISSION_REQUEST(..) => "MISSION_REQUEST",
+            &DkMessage::MISSION_SET_CURRENT(..) => "MISSION_SET_CURRENT",
+            &DkMessage::MISSION_CURRENT(..) => "MISSION_CURRENT",
+            &DkMessage::MISSION_REQUEST_LIST(..) => "MISSION_REQUEST_LIST",
+            &DkMessage::MISSION_COUNT(..) => "MISSION_COUNT",
+            &DkMessage::MISSION_CLEAR_ALL(..) => "MISSION_CLEAR_ALL",
+            &DkMessage::MISSION_ITEM_REACHED(..) => "MISSION_ITEM_REACHED",
+            &DkMessage::MISSION_ACK(..) => "MISSION_ACK",
+            &DkMessage::SET_GPS_GLOBAL_ORIGIN(..) => "SET_GPS_GLOBAL_ORIGIN",
+            &DkMessage::GPS_GLOBAL_ORIGIN(..) => "GPS_GLOBAL_ORIGIN",
+            &DkMessage::PARAM_MAP_RC(..) => "PARAM_MAP_RC",
+            &DkMessage::SAFETY_SET_ALLOWED_AREA(..) => "SAFETY_SET_ALLOWED_AREA",
+            &DkMessage::SAFETY_ALLOWED_AREA(..) => "SAFETY_ALLOWED_AREA",
+            &DkMessage::ATTITUDE_QUATERNION_COV(..) => "ATTITUDE_QUATERNION_COV",
+            &DkMessage::NAV_CONTROLLER_OUTPUT(..) => "NAV_CONTROLLER_OUTPUT",
+            &DkMessage::GLOBAL_POSITION_INT_COV(..) => "GLOBAL_POSITION_INT_COV",
+            &DkMessage::LOCAL_POSITION_NED_COV(..) => "LOCAL_POSITION_NED_COV",
+            &DkMessage::RC_CHANNELS(..) => "RC_CHANNELS",
+            &DkMessage::REQUEST_DATA_STREAM(..) => "REQUEST_DATA_STREAM",
+            &DkMessage::DATA_STREAM(..) => "DATA_STREAM",
+            &DkMessage::MANUAL_CONTROL(..) => "MANUAL_CONTROL",
+            &DkMessage::RC_CHANNELS_OVERRIDE(..) => "RC_CHANNELS_OVERRIDE",
+            &DkMessage::MISSION_ITEM_INT(..) => "MISSION_ITEM_INT",
+            &DkMessage::VFR_HUD(..) => "VFR_HUD",
+            &DkMessage::COMMAND_INT(..) => "COMMAND_INT",
+            &DkMessage::COMMAND_LONG(..) => "COMMAND_LONG",
+            &DkMessage::COMMAND_ACK(..) => "COMMAND_ACK",
+            &DkMessage::MANUAL_SETPOINT(..) => "MANUAL_SETPOINT",
+            &DkMessage::SET_ATTITUDE_TARGET(..) => "SET_ATTITUDE_TARGET",
+            &DkMessage::ATTITUDE_TARGET(..) => "ATTITUDE_TARGET",
+            &DkMessage::SET_POSITION_TARGET_LOCAL_NED(..) => "SET_POSITION_TARGET_LOCAL_NED",
+            &DkMessage::POSITION_TARGET_LOCAL_NED(..) => "POSITION_TARGET_LOCAL_NED",
+            &DkMessage::SET_POSITION_TARGET_GLOBAL_INT(..) => "SET_POSITION_TARGET_GLOBAL_INT",
+            &DkMessage::POSITION_TARGET_GLOBAL_INT(..) => "POSITION_TARGET_GLOBAL_INT",
+            &DkMessage::LOCAL_POSITION_NED_SYSTEM_GLOBAL_OFFSET(..) => "LOCAL_POSITION_NED_SYSTEM_GLOBAL_OFFSET",
+            &DkMessage::HIL_STATE(..) => "HIL_STATE",
+            &DkMessage::HIL_CONTROLS(..) => "HIL_CONTROLS",
+            &DkMessage::HIL_RC_INPUTS_RAW(..) => "HIL_RC_INPUTS_RAW",
+            &DkMessage::OPTICAL_FLOW(..) => "OPTICAL_FLOW",
+            &DkMessage::GLOBAL_VISION_POSITION_ESTIMATE(..) => "GLOBAL_VISION_POSITION_ESTIMATE",
+            &DkMessage::VISION_POSITION_ESTIMATE(..) => "VISION_POSITION_ESTIMATE",
+            &DkMessage::VISION_SPEED_ESTIMATE(..) => "VISION_SPEED_ESTIMATE",
+            &DkMessage::VICON_POSITION_ESTIMATE(..) => "VICON_POSITION_ESTIMATE",
+            &DkMessage::HIGHRES_IMU(..) => "HIGHRES_IMU",
+            &DkMessage::OPTICAL_FLOW_RAD(..) => "OPTICAL_FLOW_RAD",
+            &DkMessage::HIL_SENSOR(..) => "HIL_SENSOR",
+            &DkMessage::SIM_STATE(..) => "SIM_STATE",
+            &DkMessage::RADIO_STATUS(..) => "RADIO_STATUS",
+            &DkMessage::FILE_TRANSFER_PROTOCOL(..) => "FILE_TRANSFER_PROTOCOL",
+            &DkMessage::TIMESYNC(..) => "TIMESYNC",
+            &DkMessage::CAMERA_TRIGGER(..) => "CAMERA_TRIGGER",
+            &DkMessage::HIL_GPS(..) => "HIL_GPS",
+            &DkMessage::HIL_OPTICAL_FLOW(..) => "HIL_OPTICAL_FLOW",
+            &DkMessage::HIL_STATE_QUATERNION(..) => "HIL_STATE_QUATERNION",
+            &DkMessage::SCALED_IMU2(..) => "SCALED_IMU2",
+            &DkMessage::LOG_REQUEST_LIST(..) => "LOG_REQUEST_LIST",
+            &DkMessage::LOG_ENTRY(..) => "LOG_ENTRY",
+            &DkMessage::LOG_REQUEST_DATA(..) => "LOG_REQUEST_DATA",
+            &DkMessage::LOG_DATA(..) => "LOG_DATA",
+            &DkMessage::LOG_ERASE(..) => "LOG_ERASE",
+            &DkMessage::LOG_REQUEST_END(..) => "LOG_REQUEST_END",
+            &DkMessage::GPS_INJECT_DATA(..) => "GPS_INJECT_DATA",
+            &DkMessage::GPS2_RAW(..) => "GPS2_RAW",
+            &DkMessage::POWER_STATUS(..) => "POWER_STATUS",
+            &DkMessage::SERIAL_CONTROL(..) => "SERIAL_CONTROL",
+            &DkMessage::GPS_RTK(..) => "GPS_RTK",
+            &DkMessage::GPS2_RTK(..) => "GPS2_RTK",
+            &DkMessage::SCALED_IMU3(..) => "SCALED_IMU3",
+            &DkMessage::DATA_TRANSMISSION_HANDSHAKE(..) => "DATA_TRANSMISSION_HANDSHAKE",
+            &DkMessage::ENCAPSULATED_DATA(..) => "ENCAPSULATED_DATA",
+            &DkMessage::DISTANCE_SENSOR(..) => "DISTANCE_SENSOR",
+            &DkMessage::TERRAIN_REQUEST(..) => "TERRAIN_REQUEST",
+            &DkMessage::TERRAIN_DATA(..) => "TERRAIN_DATA",
+            &DkMessage::TERRAIN_CHECK(..) => "TERRAIN_CHECK",
+            &DkMessage::TERRAIN_REPORT(..) => "TERRAIN_REPORT",
+            &DkMessage::SCALED_PRESSURE2(..) => "SCALED_PRESSURE2",
+            &DkMessage::ATT_POS_MOCAP(..) => "ATT_POS_MOCAP",
+            &DkMessage::SET_ACTUATOR_CONTROL_TARGET(..) => "SET_ACTUATOR_CONTROL_TARGET",
+            &DkMessage::ACTUATOR_CONTROL_TARGET(..) => "ACTUATOR_CONTROL_TARGET",
+            &DkMessage::ALTITUDE(..) => "ALTITUDE",
+            &DkMessage::RESOURCE_REQUEST(..) => "RESOURCE_REQUEST",
+            &DkMessage::SCALED_PRESSURE3(..) => "SCALED_PRESSURE3",
+            &DkMessage::CONTROL_SYSTEM_STATE(..) => "CONTROL_SYSTEM_STATE",
+            &DkMessage::BATTERY_STATUS(..) => "BATTERY_STATUS",
+            &DkMessage::AUTOPILOT_VERSION(..) => "AUTOPILOT_VERSION",
+            &DkMessage::LANDING_TARGET(..) => "LANDING_TARGET",
+            &DkMessage::FENCE_POINT(..) => "FENCE_POINT",
+            &DkMessage::FENCE_FETCH_POINT(..) => "FENCE_FETCH_POINT",
+            &DkMessage::RALLY_POINT(..) => "RALLY_POINT",
+            &DkMessage::RALLY_FETCH_POINT(..) => "RALLY_FETCH_POINT",
+            &DkMessage::VIBRATION(..) => "VIBRATION",
+            &DkMessage::HOME_POSITION(..) => "HOME_POSITION",
+            &DkMessage::SET_HOME_POSITION(..) => "SET_HOME_POSITION",
+            &DkMessage::MESSAGE_INTERVAL(..) => "MESSAGE_INTERVAL",
+            &DkMessage::EXTENDED_SYS_STATE(..) => "EXTENDED_SYS_STATE",
+            &DkMessage::ADSB_VEHICLE(..) => "ADSB_VEHICLE",
+            &DkMessage::V2_EXTENSION(..) => "V2_EXTENSION",
+            &DkMessage::MEMORY_VECT(..) => "MEMORY_VECT",
+            &DkMessage::DEBUG_VECT(..) => "DEBUG_VECT",
+            &DkMessage::NAMED_VALUE_FLOAT(..) => "NAMED_VALUE_FLOAT",
+            &DkMessage::NAMED_VALUE_INT(..) => "NAMED_VALUE_INT",
+            &DkMessage::STATUSTEXT(..) => "STATUSTEXT",
+            &DkMessage::DEBUG(..) => "DEBUG",
+        }
+    }
+
     pub fn extra_crc(id: u8) -> u8 {
         match id {
             0 => 50,
@@ -8918,6 +11070,10 @@ impl DkMessage {
             147 => 154,
             148 => 178,
             149 => 200,
+            160 => 78,
+            161 => 68,
+            175 => 138,
+            176 => 234,
             241 => 90,
             242 => 104,
             243 => 85,
@@ -8935,6 +11091,54 @@ impl DkMessage {
         }
     }
 
+    // The target system and component of a message, or `None` if it is
+    // broadcast. A message with no target component addresses every
+    // component of its target system.
+    pub fn target(&self) -> Option<(u8, u8)> {
+        match self {
+            &DkMessage::PING(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::CHANGE_OPERATOR_CONTROL(ref body) => Some((body.target_system, 0)),
+            &DkMessage::SET_MODE(ref body) => Some((body.target_system, 0)),
+            &DkMessage::PARAM_REQUEST_READ(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::PARAM_REQUEST_LIST(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::PARAM_SET(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::MISSION_REQUEST_PARTIAL_LIST(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::MISSION_WRITE_PARTIAL_LIST(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::MISSION_ITEM(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::MISSION_REQUEST(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::MISSION_SET_CURRENT(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::MISSION_REQUEST_LIST(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::MISSION_COUNT(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::MISSION_CLEAR_ALL(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::MISSION_ACK(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::SET_GPS_GLOBAL_ORIGIN(ref body) => Some((body.target_system, 0)),
+            &DkMessage::PARAM_MAP_RC(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::SAFETY_SET_ALLOWED_AREA(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::REQUEST_DATA_STREAM(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::RC_CHANNELS_OVERRIDE(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::MISSION_ITEM_INT(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::COMMAND_INT(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::COMMAND_LONG(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::SET_ATTITUDE_TARGET(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::SET_POSITION_TARGET_LOCAL_NED(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::SET_POSITION_TARGET_GLOBAL_INT(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::FILE_TRANSFER_PROTOCOL(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::LOG_REQUEST_LIST(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::LOG_REQUEST_DATA(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::LOG_ERASE(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::LOG_REQUEST_END(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::GPS_INJECT_DATA(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::SET_ACTUATOR_CONTROL_TARGET(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::FENCE_POINT(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::FENCE_FETCH_POINT(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::RALLY_POINT(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::RALLY_FETCH_POINT(ref body) => Some((body.target_system, body.target_component)),
+            &DkMessage::SET_HOME_POSITION(ref body) => Some((body.target_system, 0)),
+            &DkMessage::V2_EXTENSION(ref body) => Some((body.target_system, body.target_component)),
+            _ => None,
+        }
+    }
+
     pub fn serialize(&self) -> Vec<u8> {
         match self {
             &DkMessage::HEARTBEAT(ref body) => body.serialize(),
@@ -9051,6 +11255,10 @@ impl DkMessage {
             &DkMessage::BATTERY_STATUS(ref body) => body.serialize(),
             &DkMessage::AUTOPILOT_VERSION(ref body) => body.serialize(),
             &DkMessage::LANDING_TARGET(ref body) => body.serialize(),
+            &DkMessage::FENCE_POINT(ref body) => body.serialize(),
+            &DkMessage::FENCE_FETCH_POINT(ref body) => body.serialize(),
+            &DkMessage::RALLY_POINT(ref body) => body.serialize(),
+            &DkMessage::RALLY_FETCH_POINT(ref body) => body.serialize(),
             &DkMessage::VIBRATION(ref body) => body.serialize(),
             &DkMessage::HOME_POSITION(ref body) => body.serialize(),
             &DkMessage::SET_HOME_POSITION(ref body) => body.serialize(),
@@ -9066,4 +11274,141 @@ impl DkMessage {
             &DkMessage::DEBUG(ref body) => body.serialize(),
         }
     }
+
+    // Field names, as in the message definitions, and values in wire order.
+    pub fn fields(&self) -> Vec<(&'static str, MavValue)> {
+        match self {
+            &DkMessage::HEARTBEAT(ref body) => body.fields(),
+            &DkMessage::SYS_STATUS(ref body) => body.fields(),
+            &DkMessage::SYSTEM_TIME(ref body) => body.fields(),
+            &DkMessage::PING(ref body) => body.fields(),
+            &DkMessage::CHANGE_OPERATOR_CONTROL(ref body) => body.fields(),
+            &DkMessage::CHANGE_OPERATOR_CONTROL_ACK(ref body) => body.fields(),
+            &DkMessage::AUTH_KEY(ref body) => body.fields(),
+            &DkMessage::SET_MODE(ref body) => body.fields(),
+            &DkMessage::PARAM_REQUEST_READ(ref body) => body.fields(),
+            &DkMessage::PARAM_REQUEST_LIST(ref body) => body.fields(),
+            &DkMessage::PARAM_VALUE(ref body) => body.fields(),
+            &DkMessage::PARAM_SET(ref body) => body.fields(),
+            &DkMessage::GPS_RAW_INT(ref body) => body.fields(),
+            &DkMessage::GPS_STATUS(ref body) => body.fields(),
+            &DkMessage::SCALED_IMU(ref body) => body.fields(),
+            &DkMessage::RAW_IMU(ref body) => body.fields(),
+            &DkMessage::RAW_PRESSURE(ref body) => body.fields(),
+            &DkMessage::SCALED_PRESSURE(ref body) => body.fields(),
+            &DkMessage::ATTITUDE(ref body) => body.fields(),
+            &DkMessage::ATTITUDE_QUATERNION(ref body) => body.fields(),
+            &DkMessage::LOCAL_POSITION_NED(ref body) => body.fields(),
+            &DkMessage::GLOBAL_POSITION_INT(ref body) => body.fields(),
+            &DkMessage::RC_CHANNELS_SCALED(ref body) => body.fields(),
+            &DkMessage::RC_CHANNELS_RAW(ref body) => body.fields(),
+            &DkMessage::SERVO_OUTPUT_RAW(ref body) => body.fields(),
+            &DkMessage::MISSION_REQUEST_PARTIAL_LIST(ref body) => body.fields(),
+            &DkMessage::MISSION_WRITE_PARTIAL_LIST(ref body) => body.fields(),
+            &DkMessage::MISSION_ITEM(ref body) => body.fields(),
+            &DkMessage::MISSION_REQUEST(ref body) => body.fields(),
+            &DkMessage::MISSION_SET_CURRENT(ref body) => body.fields(),
+            &DkMessage::MISSION_CURRENT(ref body) => body.fields(),
+            &DkMessage::MISSION_REQUEST_LIST(ref body) => body.fields(),
+            &DkMessage::MISSION_COUNT(ref body) => body.fields(),
+            &DkMessage::MISSION_CLEAR_ALL(ref body) => body.fields(),
+            &DkMessage::MISSION_ITEM_REACHED(ref body) => body.fields(),
+            &DkMessage::MISSION_ACK(ref body) => body.fields(),
+            &DkMessage::SET_GPS_GLOBAL_ORIGIN(ref body) => body.fields(),
+            &DkMessage::GPS_GLOBAL_ORIGIN(ref body) => body.fields(),
+            &DkMessage::PARAM_MAP_RC(ref body) => body.fields(),
+            &DkMessage::SAFETY_SET_ALLOWED_AREA(ref body) => body.fields(),
+            &DkMessage::SAFETY_ALLOWED_AREA(ref body) => body.fields(),
+            &DkMessage::ATTITUDE_QUATERNION_COV(ref body) => body.fields(),
+            &DkMessage::NAV_CONTROLLER_OUTPUT(ref body) => body.fields(),
+            &DkMessage::GLOBAL_POSITION_INT_COV(ref body) => body.fields(),
+            &DkMessage::LOCAL_POSITION_NED_COV(ref body) => body.fields(),
+            &DkMessage::RC_CHANNELS(ref body) => body.fields(),
+            &DkMessage::REQUEST_DATA_STREAM(ref body) => body.fields(),
+            &DkMessage::DATA_STREAM(ref body) => body.fields(),
+            &DkMessage::MANUAL_CONTROL(ref body) => body.fields(),
+            &DkMessage::RC_CHANNELS_OVERRIDE(ref body) => body.fields(),
+            &DkMessage::MISSION_ITEM_INT(ref body) => body.fields(),
+            &DkMessage::VFR_HUD(ref body) => body.fields(),
+            &DkMessage::COMMAND_INT(ref body) => body.fields(),
+            &DkMessage::COMMAND_LONG(ref body) => body.fields(),
+            &DkMessage::COMMAND_ACK(ref body) => body.fields(),
+            &DkMessage::MANUAL_SETPOINT(ref body) => body.fields(),
+            &DkMessage::SET_ATTITUDE_TARGET(ref body) => body.fields(),
+            &DkMessage::ATTITUDE_TARGET(ref body) => body.fields(),
+            &DkMessage::SET_POSITION_TARGET_LOCAL_NED(ref body) => body.fields(),
+            &DkMessage::POSITION_TARGET_LOCAL_NED(ref body) => body.fields(),
+            &DkMessage::SET_POSITION_TARGET_GLOBAL_INT(ref body) => body.fields(),
+            &DkMessage::POSITION_TARGET_GLOBAL_INT(ref body) => body.fields(),
+            &DkMessage::LOCAL_POSITION_NED_SYSTEM_GLOBAL_OFFSET(ref body) => body.fields(),
+            &DkMessage::HIL_STATE(ref body) => body.fields(),
+            &DkMessage::HIL_CONTROLS(ref body) => body.fields(),
+            &DkMessage::HIL_RC_INPUTS_RAW(ref body) => body.fields(),
+            &DkMessage::OPTICAL_FLOW(ref body) => body.fields(),
+            &DkMessage::GLOBAL_VISION_POSITION_ESTIMATE(ref body) => body.fields(),
+            &DkMessage::VISION_POSITION_ESTIMATE(ref body) => body.fields(),
+            &DkMessage::VISION_SPEED_ESTIMATE(ref body) => body.fields(),
+            &DkMessage::VICON_POSITION_ESTIMATE(ref body) => body.fields(),
+            &DkMessage::HIGHRES_IMU(ref body) => body.fields(),
+            &DkMessage::OPTICAL_FLOW_RAD(ref body) => body.fields(),
+            &DkMessage::HIL_SENSOR(ref body) => body.fields(),
+            &DkMessage::SIM_STATE(ref body) => body.fields(),
+            &DkMessage::RADIO_STATUS(ref body) => body.fields(),
+            &DkMessage::FILE_TRANSFER_PROTOCOL(ref body) => body.fields(),
+            &DkMessage::TIMESYNC(ref body) => body.fields(),
+            &DkMessage::CAMERA_TRIGGER(ref body) => body.fields(),
+            &DkMessage::HIL_GPS(ref body) => body.fields(),
+            &DkMessage::HIL_OPTICAL_FLOW(ref body) => body.fields(),
+            &DkMessage::HIL_STATE_QUATERNION(ref body) => body.fields(),
+            &DkMessage::SCALED_IMU2(ref body) => body.fields(),
+            &DkMessage::LOG_REQUEST_LIST(ref body) => body.fields(),
+            &DkMessage::LOG_ENTRY(ref body) => body.fields(),
+            &DkMessage::LOG_REQUEST_DATA(ref body) => body.fields(),
+            &DkMessage::LOG_DATA(ref body) => body.fields(),
+            &DkMessage::LOG_ERASE(ref body) => body.fields(),
+            &DkMessage::LOG_REQUEST_END(ref body) => body.fields(),
+            &DkMessage::GPS_INJECT_DATA(ref body) => body.fields(),
+            &DkMessage::GPS2_RAW(ref body) => body.fields(),
+            &DkMessage::POWER_STATUS(ref body) => body.fields(),
+            &DkMessage::SERIAL_CONTROL(ref body) => body.fields(),
+            &DkMessage::GPS_RTK(ref body) => body.fields(),
+            &DkMessage::GPS2_RTK(ref body) => body.fields(),
+            &DkMessage::SCALED_IMU3(ref body) => body.fields(),
+            &DkMessage::DATA_TRANSMISSION_HANDSHAKE(ref body) => body.fields(),
+            &DkMessage::ENCAPSULATED_DATA(ref body) => body.fields(),
+            &DkMessage::DISTANCE_SENSOR(ref body) => body.fields(),
+            &DkMessage::TERRAIN_REQUEST(ref body) => body.fields(),
+            &DkMessage::TERRAIN_DATA(ref body) => body.fields(),
+            &DkMessage::TERRAIN_CHECK(ref body) => body.fields(),
+            &DkMessage::TERRAIN_REPORT(ref body) => body.fields(),
+            &DkMessage::SCALED_PRESSURE2(ref body) => body.fields(),
+            &DkMessage::ATT_POS_MOCAP(ref body) => body.fields(),
+            &DkMessage::SET_ACTUATOR_CONTROL_TARGET(ref body) => body.fields(),
+            &DkMessage::ACTUATOR_CONTROL_TARGET(ref body) => body.fields(),
+            &DkMessage::ALTITUDE(ref body) => body.fields(),
+            &DkMessage::RESOURCE_REQUEST(ref body) => body.fields(),
+            &DkMessage::SCALED_PRESSURE3(ref body) => body.fields(),
+            &DkMessage::CONTROL_SYSTEM_STATE(ref body) => body.fields(),
+            &DkMessage::BATTERY_STATUS(ref body) => body.fields(),
+            &DkMessage::AUTOPILOT_VERSION(ref body) => body.fields(),
+            &DkMessage::LANDING_TARGET(ref body) => body.fields(),
+            &DkMessage::FENCE_POINT(ref body) => body.fields(),
+            &DkMessage::FENCE_FETCH_POINT(ref body) => body.fields(),
+            &DkMessage::RALLY_POINT(ref body) => body.fields(),
+            &DkMessage::RALLY_FETCH_POINT(ref body) => body.fields(),
+            &DkMessage::VIBRATION(ref body) => body.fields(),
+            &DkMessage::HOME_POSITION(ref body) => body.fields(),
+            &DkMessage::SET_HOME_POSITION(ref body) => body.fields(),
+            &DkMessage::MESSAGE_INTERVAL(ref body) => body.fields(),
+            &DkMessage::EXTENDED_SYS_STATE(ref body) => body.fields(),
+            &DkMessage::ADSB_VEHICLE(ref body) => body.fields(),
+            &DkMessage::V2_EXTENSION(ref body) => body.fields(),
+            &DkMessage::MEMORY_VECT(ref body) => body.fields(),
+            &DkMessage::DEBUG_VECT(ref body) => body.fields(),
+            &DkMessage::NAMED_VALUE_FLOAT(ref body) => body.fields(),
+            &DkMessage::NAMED_VALUE_INT(ref body) => body.fields(),
+            &DkMessage::STATUSTEXT(ref body) => body.fields(),
+            &DkMessage::DEBUG(ref body) => body.fields(),
+        }
+    }
 }
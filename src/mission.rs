@@ -0,0 +1,630 @@
+use mavlink::*;
+
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::iter::repeat;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use eventual::{Complete, Future};
+use time;
+
+use connection::{VehicleConnection, Outbox};
+
+// How long a transfer may go without progress before the last message is
+// resent, and how many times it is sent before giving up, as for fence and
+// rally points.
+const MISSION_QUIET_NS: u64 = 1_000_000_000;
+const MISSION_ATTEMPTS: usize = 3;
+
+// When a transfer last made progress, and how often the current message has
+// been sent.
+struct Retry {
+    attempts: usize,
+    last_sent: u64,
+}
+
+impl Retry {
+    fn new() -> Retry {
+        Retry {
+            attempts: 1,
+            last_sent: time::precise_time_ns(),
+        }
+    }
+
+    // Records progress, after which a new message is sent.
+    fn reset(&mut self) {
+        self.attempts = 1;
+        self.last_sent = time::precise_time_ns();
+    }
+
+    // Called for messages that don't advance the transfer. Calls `resend`
+    // after a quiet period, and fails `tx` once attempts run out. Returns
+    // whether the transfer is over.
+    fn stalled<T, F>(&mut self, tx: &mut Option<Complete<T, ()>>, resend: F) -> bool
+        where T: Send + 'static,
+              F: FnOnce()
+    {
+        let now = time::precise_time_ns();
+        if now - self.last_sent < MISSION_QUIET_NS {
+            return false;
+        }
+        if self.attempts >= MISSION_ATTEMPTS {
+            if let Some(tx) = tx.take() {
+                tx.fail(());
+            }
+            return true;
+        }
+        self.attempts += 1;
+        self.last_sent = now;
+        resend();
+        false
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MissionItem {
+    pub command: u16,
+    pub frame: u8,
+    pub param1: f32,
+    pub param2: f32,
+    pub param3: f32,
+    pub param4: f32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub autocontinue: u8,
+}
+
+impl MissionItem {
+    pub fn from_data(data: &MISSION_ITEM_DATA) -> MissionItem {
+        MissionItem {
+            command: data.command,
+            frame: data.frame,
+            param1: data.param1,
+            param2: data.param2,
+            param3: data.param3,
+            param4: data.param4,
+            x: data.x,
+            y: data.y,
+            z: data.z,
+            autocontinue: data.autocontinue,
+        }
+    }
+
+    pub fn to_data(&self, seq: u16, target_system: u8, target_component: u8) -> MISSION_ITEM_DATA {
+        MISSION_ITEM_DATA {
+            param1: self.param1,
+            param2: self.param2,
+            param3: self.param3,
+            param4: self.param4,
+            x: self.x,
+            y: self.y,
+            z: self.z,
+            seq: seq,
+            command: self.command,
+            target_system: target_system,
+            target_component: target_component,
+            frame: self.frame,
+            current: 0,
+            autocontinue: self.autocontinue,
+        }
+    }
+
+    // Parses a line of a QGroundControl waypoint file:
+    // <seq> <current> <frame> <command> <p1> <p2> <p3> <p4> <x> <y> <z> <autocontinue>
+    fn parse_line(line: &str) -> Option<MissionItem> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 12 {
+            return None;
+        }
+        let params: Option<Vec<f32>> = fields[4..11]
+                                           .iter()
+                                           .map(|x| x.parse::<f64>().ok().map(|x| x as f32))
+                                           .collect();
+        match (fields[2].parse::<u8>().ok(),
+               fields[3].parse::<u16>().ok(),
+               params,
+               fields[11].parse::<u8>().ok()) {
+            (Some(frame), Some(command), Some(p), Some(autocontinue)) => {
+                Some(MissionItem {
+                    command: command,
+                    frame: frame,
+                    param1: p[0],
+                    param2: p[1],
+                    param3: p[2],
+                    param4: p[3],
+                    x: p[4],
+                    y: p[5],
+                    z: p[6],
+                    autocontinue: autocontinue,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn format_line(&self, seq: usize) -> String {
+        format!("{}\t{}\t{}\t{}\t{:.8}\t{:.8}\t{:.8}\t{:.8}\t{:.8}\t{:.8}\t{:.6}\t{}",
+                seq,
+                if seq == 0 { 1 } else { 0 },
+                self.frame,
+                self.command,
+                self.param1,
+                self.param2,
+                self.param3,
+                self.param4,
+                self.x,
+                self.y,
+                self.z,
+                self.autocontinue)
+    }
+}
+
+const WPL_HEADER: &'static str = "QGC WPL 110";
+
+// Reads a QGroundControl waypoint file. Item 0 is the home position.
+pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Vec<MissionItem>> {
+    let file = BufReader::new(try!(File::open(path)));
+    let mut items = vec![];
+    for (i, line) in file.lines().enumerate() {
+        let line = try!(line);
+        let line = line.trim();
+        if i == 0 {
+            if !line.starts_with(WPL_HEADER) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          "not a QGC WPL 110 waypoint file"));
+            }
+            continue;
+        }
+        if line.is_empty() {
+            continue;
+        }
+        match MissionItem::parse_line(line) {
+            Some(item) => items.push(item),
+            None => {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          format!("invalid waypoint: {:?}", line)))
+            }
+        }
+    }
+    Ok(items)
+}
+
+pub fn save<P: AsRef<Path>>(path: P, items: &[MissionItem]) -> io::Result<()> {
+    let mut file = try!(File::create(path));
+    try!(writeln!(file, "{}", WPL_HEADER));
+    for (i, item) in items.iter().enumerate() {
+        try!(writeln!(file, "{}", item.format_line(i)));
+    }
+    Ok(())
+}
+
+// An inclusive range of mission item indexes, as used by the partial list
+// messages.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MissionRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl MissionRange {
+    // Whether the range is in order and within a mission of `count` items.
+    // The partial list messages carry indexes as `i16`.
+    pub fn is_valid(&self, count: usize) -> bool {
+        self.start <= self.end && (self.end as usize) < count && self.end <= i16::MAX as u16
+    }
+}
+
+// Computes the ranges of `desired` that differ from `cached`. Partial writes
+// cannot change the length of a mission, so `None` is returned when the two
+// differ in length and a full upload is required.
+pub fn diff(cached: &[MissionItem], desired: &[MissionItem]) -> Option<Vec<MissionRange>> {
+    if cached.len() != desired.len() {
+        return None;
+    }
+
+    let mut ranges: Vec<MissionRange> = vec![];
+    for (i, (a, b)) in cached.iter().zip(desired.iter()).enumerate() {
+        if a == b {
+            continue;
+        }
+        let i = i as u16;
+        if let Some(range) = ranges.last_mut() {
+            if range.end + 1 == i {
+                range.end = i;
+                continue;
+            }
+        }
+        ranges.push(MissionRange { start: i, end: i });
+    }
+    Some(ranges)
+}
+
+#[derive(Clone)]
+pub struct Mission {
+    items: Arc<Mutex<Vec<Option<MissionItem>>>>,
+    connection: Rc<RefCell<VehicleConnection>>,
+}
+
+impl Mission {
+    pub fn new(connection: Rc<RefCell<VehicleConnection>>) -> Mission {
+        Mission {
+            items: Arc::new(Mutex::new(vec![])),
+            connection: connection,
+        }
+    }
+
+    // Returns the cached mission, if every item has been downloaded.
+    pub fn items(&self) -> Option<Vec<MissionItem>> {
+        self.items.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn count(&self) -> usize {
+        self.items.lock().unwrap().len()
+    }
+
+    // Downloads the full mission into the cache.
+    pub fn fetch(&mut self) -> Future<Vec<MissionItem>, ()> {
+        let (tx, future) = Future::<Vec<MissionItem>, ()>::pair();
+
+        let mut conn = self.connection.borrow_mut();
+        let outbox = conn.outbox();
+        let cache = self.items.clone();
+
+        let mut tx = Some(tx);
+        let mut retry = Retry::new();
+        let mut counted = false;
+        let mut received: Vec<Option<MissionItem>> = vec![];
+        let mut next = 0;
+        conn.watch(Box::new(move |msg| {
+            match msg {
+                DkMessage::MISSION_COUNT(data) => {
+                    received = repeat(None).take(data.count as usize).collect();
+                    counted = true;
+                    next = 0;
+                }
+                DkMessage::MISSION_ITEM(ref data) if counted && data.seq as usize == next &&
+                                                     next < received.len() => {
+                    received[next] = Some(MissionItem::from_data(data));
+                    next += 1;
+                }
+                _ => {
+                    return retry.stalled(&mut tx, || {
+                        if counted {
+                            send_request(&outbox, next as u16);
+                        } else {
+                            send_request_list(&outbox);
+                        }
+                    });
+                }
+            }
+            retry.reset();
+
+            if next < received.len() {
+                send_request(&outbox, next as u16);
+                return false;
+            }
+
+            outbox.send(DkMessage::MISSION_ACK(MISSION_ACK_DATA {
+                target_system: outbox.target_system,
+                target_component: outbox.target_component,
+                mavtype: 0,
+            }));
+            *cache.lock().unwrap() = received.clone();
+            if let Some(tx) = tx.take() {
+                tx.complete(received.iter().cloned().map(|x| x.unwrap()).collect());
+            }
+            true
+        }));
+
+        send_request_list(&conn.outbox());
+
+        future
+    }
+
+    // Downloads items `start` through `end` inclusive, updating the cache.
+    // Fails without sending anything unless the range is valid for the
+    // cached mission count, so the mission must have been fetched first.
+    pub fn fetch_partial(&mut self, range: MissionRange) -> Future<Vec<MissionItem>, ()> {
+        if !range.is_valid(self.count()) {
+            return Future::error(());
+        }
+
+        let (tx, future) = Future::<Vec<MissionItem>, ()>::pair();
+
+        let mut conn = self.connection.borrow_mut();
+        let outbox = conn.outbox();
+        let cache = self.items.clone();
+
+        let mut tx = Some(tx);
+        let mut retry = Retry::new();
+        let mut received = vec![];
+        let mut next = range.start;
+        conn.watch(Box::new(move |msg| {
+            // Vehicles may either stream the range or wait for individual
+            // requests; ignore anything out of order.
+            match msg {
+                DkMessage::MISSION_ITEM(ref data) if data.seq == next => {
+                    received.push(MissionItem::from_data(data));
+                }
+                _ => return retry.stalled(&mut tx, || send_request(&outbox, next)),
+            }
+            retry.reset();
+
+            if next < range.end {
+                next += 1;
+                send_request(&outbox, next);
+                return false;
+            }
+
+            {
+                let mut cache = cache.lock().unwrap();
+                let len = cmp::max(cache.len(), range.end as usize + 1);
+                cache.resize(len, None);
+                for (i, item) in received.iter().enumerate() {
+                    cache[range.start as usize + i] = Some(item.clone());
+                }
+            }
+            if let Some(tx) = tx.take() {
+                tx.complete(received.clone());
+            }
+            true
+        }));
+
+        conn.send(DkMessage::MISSION_REQUEST_PARTIAL_LIST(MISSION_REQUEST_PARTIAL_LIST_DATA {
+            start_index: range.start as i16,
+            end_index: range.end as i16,
+            target_system: conn.target_system,
+            target_component: conn.target_component,
+        }));
+        send_request(&conn.outbox(), range.start);
+
+        future
+    }
+
+    // Writes each range of `items` (indexed by absolute sequence number) using
+    // MISSION_WRITE_PARTIAL_LIST, one transaction at a time. The cache is
+    // updated as each range is acknowledged. Fails without sending anything
+    // if a range isn't valid for `items`.
+    pub fn write_partial(&mut self,
+                         items: Vec<MissionItem>,
+                         ranges: Vec<MissionRange>)
+                         -> Future<(), ()> {
+        if ranges.iter().any(|range| !range.is_valid(items.len())) {
+            return Future::error(());
+        }
+
+        let (tx, future) = Future::<(), ()>::pair();
+
+        let mut ranges: VecDeque<MissionRange> = ranges.into_iter().collect();
+        let first = match ranges.pop_front() {
+            Some(range) => range,
+            None => {
+                tx.complete(());
+                return future;
+            }
+        };
+
+        let mut conn = self.connection.borrow_mut();
+        let outbox = conn.outbox();
+        let cache = self.items.clone();
+
+        let mut tx = Some(tx);
+        let mut retry = Retry::new();
+        let mut current = first;
+        // The item last sent, which is resent if the vehicle goes quiet.
+        let mut last_item = None;
+        conn.watch(Box::new(move |msg| {
+            match msg {
+                DkMessage::MISSION_REQUEST(ref data) if data.seq >= current.start &&
+                                                        data.seq <= current.end => {
+                    send_item(&outbox, &items, data.seq);
+                    last_item = Some(data.seq);
+                    retry.reset();
+                    false
+                }
+                DkMessage::MISSION_ACK(data) => {
+                    if data.mavtype != 0 {
+                        if let Some(tx) = tx.take() {
+                            tx.fail(());
+                        }
+                        return true;
+                    }
+
+                    {
+                        let mut cache = cache.lock().unwrap();
+                        for i in current.start..=current.end {
+                            if let (Some(slot), Some(item)) = (cache.get_mut(i as usize),
+                                                               items.get(i as usize)) {
+                                *slot = Some(item.clone());
+                            }
+                        }
+                    }
+
+                    match ranges.pop_front() {
+                        Some(range) => {
+                            current = range;
+                            last_item = None;
+                            retry.reset();
+                            send_write_partial(&outbox, current);
+                            false
+                        }
+                        None => {
+                            if let Some(tx) = tx.take() {
+                                tx.complete(());
+                            }
+                            true
+                        }
+                    }
+                }
+                _ => {
+                    retry.stalled(&mut tx, || {
+                        match last_item {
+                            Some(seq) => send_item(&outbox, &items, seq),
+                            None => send_write_partial(&outbox, current),
+                        }
+                    })
+                }
+            }
+        }));
+
+        send_write_partial(&conn.outbox(), first);
+
+        future
+    }
+
+    // Replaces the whole mission, which may change its length.
+    pub fn upload(&mut self, items: Vec<MissionItem>) -> Future<(), ()> {
+        if items.len() > u16::MAX as usize {
+            return Future::error(());
+        }
+
+        let (tx, future) = Future::<(), ()>::pair();
+
+        let mut conn = self.connection.borrow_mut();
+        let outbox = conn.outbox();
+        let cache = self.items.clone();
+
+        let mut tx = Some(tx);
+        let mut retry = Retry::new();
+        let mut last_item = None;
+        let count = items.len() as u16;
+        conn.watch(Box::new(move |msg| {
+            match msg {
+                DkMessage::MISSION_REQUEST(ref data) if (data.seq as usize) < items.len() => {
+                    send_item(&outbox, &items, data.seq);
+                    last_item = Some(data.seq);
+                    retry.reset();
+                    false
+                }
+                DkMessage::MISSION_ACK(data) => {
+                    if let Some(tx) = tx.take() {
+                        if data.mavtype == 0 {
+                            *cache.lock().unwrap() = items.iter().cloned().map(Some).collect();
+                            tx.complete(());
+                        } else {
+                            tx.fail(());
+                        }
+                    }
+                    true
+                }
+                _ => {
+                    retry.stalled(&mut tx, || {
+                        match last_item {
+                            Some(seq) => send_item(&outbox, &items, seq),
+                            None => send_count(&outbox, count),
+                        }
+                    })
+                }
+            }
+        }));
+
+        send_count(&conn.outbox(), count);
+
+        future
+    }
+
+    // Rewrites only the items of `desired` that differ from the cache. Fails
+    // if the cache is incomplete or the mission length would change.
+    pub fn update(&mut self, desired: Vec<MissionItem>) -> Future<(), ()> {
+        let ranges = self.items().and_then(|cached| diff(&cached, &desired));
+        match ranges {
+            Some(ranges) => self.write_partial(desired, ranges),
+            None => Future::error(()),
+        }
+    }
+}
+
+fn send_request_list(outbox: &Outbox) {
+    outbox.send(DkMessage::MISSION_REQUEST_LIST(MISSION_REQUEST_LIST_DATA {
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+    }));
+}
+
+fn send_request(outbox: &Outbox, seq: u16) {
+    outbox.send(DkMessage::MISSION_REQUEST(MISSION_REQUEST_DATA {
+        seq: seq,
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+    }));
+}
+
+fn send_count(outbox: &Outbox, count: u16) {
+    outbox.send(DkMessage::MISSION_COUNT(MISSION_COUNT_DATA {
+        count: count,
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+    }));
+}
+
+// Sends item `seq` of `items`, which the caller has checked is in range.
+fn send_item(outbox: &Outbox, items: &[MissionItem], seq: u16) {
+    outbox.send(DkMessage::MISSION_ITEM(items[seq as usize].to_data(seq,
+                                                                   outbox.target_system,
+                                                                   outbox.target_component)));
+}
+
+fn send_write_partial(outbox: &Outbox, range: MissionRange) {
+    outbox.send(DkMessage::MISSION_WRITE_PARTIAL_LIST(MISSION_WRITE_PARTIAL_LIST_DATA {
+        start_index: range.start as i16,
+        end_index: range.end as i16,
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+    }));
+}
+
+#[test]
+fn test_diff() {
+    let item = |x: f32| {
+        MissionItem {
+            command: 16,
+            frame: 3,
+            param1: 0.0,
+            param2: 0.0,
+            param3: 0.0,
+            param4: 0.0,
+            x: x,
+            y: 0.0,
+            z: 10.0,
+            autocontinue: 1,
+        }
+    };
+
+    let cached: Vec<MissionItem> = (0..8).map(|i| item(i as f32)).collect();
+    let mut desired = cached.clone();
+    desired[2] = item(20.0);
+    desired[3] = item(30.0);
+    desired[6] = item(60.0);
+
+    assert_eq!(diff(&cached, &desired),
+               Some(vec![MissionRange { start: 2, end: 3 }, MissionRange { start: 6, end: 6 }]));
+    assert_eq!(diff(&cached, &cached), Some(vec![]));
+    assert_eq!(diff(&cached, &desired[..4]), None);
+}
+
+#[test]
+fn test_range_valid() {
+    let range = |start, end| MissionRange { start: start, end: end };
+    assert!(range(0, 0).is_valid(1));
+    assert!(range(2, 4).is_valid(5));
+    assert!(!range(2, 5).is_valid(5));
+    assert!(!range(4, 2).is_valid(5));
+    assert!(!range(0, 0).is_valid(0));
+    assert!(!range(0, u16::MAX).is_valid(70000));
+}
+
+#[test]
+fn test_waypoint_line() {
+    let item = MissionItem::parse_line("1\t0\t3\t16\t0.0\t0.0\t0.0\t0.0\t-35.3632622\t149.1652374\t20.0\t1")
+                   .unwrap();
+    assert_eq!(item.command, 16);
+    assert_eq!(item.frame, 3);
+    assert_eq!(item.z, 20.0);
+    assert_eq!(MissionItem::parse_line(&item.format_line(1)), Some(item));
+    assert_eq!(MissionItem::parse_line("1\t0\t3\t16"), None);
+}
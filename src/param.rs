@@ -0,0 +1,212 @@
+use std::fmt;
+
+pub const MAV_PARAM_TYPE_UINT8: u8 = 1;
+pub const MAV_PARAM_TYPE_INT8: u8 = 2;
+pub const MAV_PARAM_TYPE_UINT16: u8 = 3;
+pub const MAV_PARAM_TYPE_INT16: u8 = 4;
+pub const MAV_PARAM_TYPE_UINT32: u8 = 5;
+pub const MAV_PARAM_TYPE_INT32: u8 = 6;
+pub const MAV_PARAM_TYPE_REAL32: u8 = 9;
+
+const MAV_AUTOPILOT_PX4: u8 = 12;
+
+// How integer parameters are carried in the float `param_value` field.
+// ArduPilot converts the value to a float, while PX4 copies its bytes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParamEncoding {
+    CastFloat,
+    Bytewise,
+}
+
+impl ParamEncoding {
+    // Picks the encoding for the autopilot type reported in HEARTBEAT.
+    pub fn for_autopilot(autopilot: u8) -> ParamEncoding {
+        if autopilot == MAV_AUTOPILOT_PX4 {
+            ParamEncoding::Bytewise
+        } else {
+            ParamEncoding::CastFloat
+        }
+    }
+}
+
+// Describes a change to a cached parameter value. `local` is set when the
+// change was requested through this `Parameters` instance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParamChangeEvent {
+    pub name: String,
+    pub old: ParamValue,
+    pub new: ParamValue,
+    pub local: bool,
+}
+
+// Why setting a parameter failed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamError {
+    // The value is outside the range given by loaded metadata, and was not
+    // sent.
+    Invalid(String),
+    // The vehicle kept a different value.
+    Rejected(ParamValue),
+    // The vehicle stopped replying.
+    NoReply,
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParamError::Invalid(ref reason) => write!(f, "{}", reason),
+            ParamError::Rejected(value) => write!(f, "rejected, value is {}", value),
+            ParamError::NoReply => write!(f, "no reply"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParamValue {
+    U8(u8),
+    I8(i8),
+    U16(u16),
+    I16(i16),
+    U32(u32),
+    I32(i32),
+    F32(f32),
+}
+
+impl ParamValue {
+    // Decodes a PARAM_VALUE payload. Unsupported types are kept as floats.
+    pub fn decode(raw: f32, param_type: u8, encoding: ParamEncoding) -> ParamValue {
+        match encoding {
+            ParamEncoding::CastFloat => {
+                match param_type {
+                    MAV_PARAM_TYPE_UINT8 => ParamValue::U8(raw as u8),
+                    MAV_PARAM_TYPE_INT8 => ParamValue::I8(raw as i8),
+                    MAV_PARAM_TYPE_UINT16 => ParamValue::U16(raw as u16),
+                    MAV_PARAM_TYPE_INT16 => ParamValue::I16(raw as i16),
+                    MAV_PARAM_TYPE_UINT32 => ParamValue::U32(raw as u32),
+                    MAV_PARAM_TYPE_INT32 => ParamValue::I32(raw as i32),
+                    _ => ParamValue::F32(raw),
+                }
+            }
+            ParamEncoding::Bytewise => {
+                let bits = raw.to_bits();
+                match param_type {
+                    MAV_PARAM_TYPE_UINT8 => ParamValue::U8(bits as u8),
+                    MAV_PARAM_TYPE_INT8 => ParamValue::I8(bits as u8 as i8),
+                    MAV_PARAM_TYPE_UINT16 => ParamValue::U16(bits as u16),
+                    MAV_PARAM_TYPE_INT16 => ParamValue::I16(bits as u16 as i16),
+                    MAV_PARAM_TYPE_UINT32 => ParamValue::U32(bits),
+                    MAV_PARAM_TYPE_INT32 => ParamValue::I32(bits as i32),
+                    _ => ParamValue::F32(raw),
+                }
+            }
+        }
+    }
+
+    // Encodes the value for PARAM_SET, returning `param_value` and
+    // `param_type`.
+    pub fn encode(&self, encoding: ParamEncoding) -> (f32, u8) {
+        let param_type = self.param_type();
+        let raw = match encoding {
+            ParamEncoding::CastFloat => self.as_f32(),
+            ParamEncoding::Bytewise => {
+                let bits = match *self {
+                    ParamValue::U8(x) => x as u32,
+                    ParamValue::I8(x) => x as u8 as u32,
+                    ParamValue::U16(x) => x as u32,
+                    ParamValue::I16(x) => x as u16 as u32,
+                    ParamValue::U32(x) => x,
+                    ParamValue::I32(x) => x as u32,
+                    ParamValue::F32(x) => x.to_bits(),
+                };
+                f32::from_bits(bits)
+            }
+        };
+        (raw, param_type)
+    }
+
+    // Converts a float to this value's type, as when setting a parameter
+    // whose type is already known.
+    pub fn with_value(&self, value: f32) -> ParamValue {
+        self.with_value_f64(value as f64)
+    }
+
+    // Like `with_value`, but keeps integers beyond f32 precision, such as
+    // those read from a parameter file.
+    pub fn with_value_f64(&self, value: f64) -> ParamValue {
+        match *self {
+            ParamValue::U8(_) => ParamValue::U8(value as u8),
+            ParamValue::I8(_) => ParamValue::I8(value as i8),
+            ParamValue::U16(_) => ParamValue::U16(value as u16),
+            ParamValue::I16(_) => ParamValue::I16(value as i16),
+            ParamValue::U32(_) => ParamValue::U32(value as u32),
+            ParamValue::I32(_) => ParamValue::I32(value as i32),
+            ParamValue::F32(_) => ParamValue::F32(value as f32),
+        }
+    }
+
+    pub fn param_type(&self) -> u8 {
+        match *self {
+            ParamValue::U8(_) => MAV_PARAM_TYPE_UINT8,
+            ParamValue::I8(_) => MAV_PARAM_TYPE_INT8,
+            ParamValue::U16(_) => MAV_PARAM_TYPE_UINT16,
+            ParamValue::I16(_) => MAV_PARAM_TYPE_INT16,
+            ParamValue::U32(_) => MAV_PARAM_TYPE_UINT32,
+            ParamValue::I32(_) => MAV_PARAM_TYPE_INT32,
+            ParamValue::F32(_) => MAV_PARAM_TYPE_REAL32,
+        }
+    }
+
+    pub fn as_f32(&self) -> f32 {
+        match *self {
+            ParamValue::U8(x) => x as f32,
+            ParamValue::I8(x) => x as f32,
+            ParamValue::U16(x) => x as f32,
+            ParamValue::I16(x) => x as f32,
+            ParamValue::U32(x) => x as f32,
+            ParamValue::I32(x) => x as f32,
+            ParamValue::F32(x) => x,
+        }
+    }
+
+    // Unlike `as_f32`, exact for every type.
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            ParamValue::U8(x) => x as f64,
+            ParamValue::I8(x) => x as f64,
+            ParamValue::U16(x) => x as f64,
+            ParamValue::I16(x) => x as f64,
+            ParamValue::U32(x) => x as f64,
+            ParamValue::I32(x) => x as f64,
+            ParamValue::F32(x) => x as f64,
+        }
+    }
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParamValue::U8(x) => write!(f, "{}", x),
+            ParamValue::I8(x) => write!(f, "{}", x),
+            ParamValue::U16(x) => write!(f, "{}", x),
+            ParamValue::I16(x) => write!(f, "{}", x),
+            ParamValue::U32(x) => write!(f, "{}", x),
+            ParamValue::I32(x) => write!(f, "{}", x),
+            ParamValue::F32(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+#[test]
+fn test_encoding() {
+    let big = ParamValue::I32(16777217);
+    let (raw, param_type) = big.encode(ParamEncoding::Bytewise);
+    assert_eq!(ParamValue::decode(raw, param_type, ParamEncoding::Bytewise), big);
+
+    let (raw, param_type) = ParamValue::I8(-3).encode(ParamEncoding::CastFloat);
+    assert_eq!(raw, -3.0);
+    assert_eq!(ParamValue::decode(raw, param_type, ParamEncoding::CastFloat),
+               ParamValue::I8(-3));
+
+    assert_eq!(ParamValue::I32(0).with_value_f64(16777217.0), big);
+    assert_eq!(ParamValue::F32(0.0).with_value_f64(0.1), ParamValue::F32(0.1));
+}
@@ -0,0 +1,98 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use param::ParamValue;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParamFileFormat {
+    // NAME,VALUE
+    MissionPlanner,
+    // NAME VALUE
+    MAVProxy,
+}
+
+impl ParamFileFormat {
+    // Mission Planner uses `.param`; anything else is written MAVProxy style.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> ParamFileFormat {
+        match path.as_ref().extension().and_then(|x| x.to_str()) {
+            Some("param") => ParamFileFormat::MissionPlanner,
+            _ => ParamFileFormat::MAVProxy,
+        }
+    }
+}
+
+// A parameter whose live value differs from the desired one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParamChange {
+    pub name: String,
+    pub current: Option<ParamValue>,
+    pub desired: ParamValue,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApplySummary {
+    pub applied: Vec<String>,
+    // Parameters that were rejected, with the value the vehicle reported (or
+    // the cached value if metadata rejected it first), or `None` if the
+    // vehicle never replied.
+    pub failed: Vec<(String, Option<ParamValue>)>,
+}
+
+// Values are read as f64 so that integers beyond f32 precision survive until
+// they are converted to the parameter's type.
+fn parse_line(line: &str) -> Option<(String, f64)> {
+    // Strip trailing comments, which MAVProxy writes after the value.
+    let line = line.split('#').next().unwrap_or("").trim();
+    let mut fields = line.split(|c: char| c == ',' || c.is_whitespace())
+                         .filter(|x| !x.is_empty());
+    match (fields.next(), fields.next()) {
+        (Some(name), Some(value)) => value.parse::<f64>().ok().map(|x| (name.into(), x)),
+        _ => None,
+    }
+}
+
+// Loads a Mission Planner or MAVProxy parameter file. Both separators are
+// accepted regardless of extension.
+pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Vec<(String, f64)>> {
+    let file = BufReader::new(try!(File::open(path)));
+    let mut params = vec![];
+    for line in file.lines() {
+        let line = try!(line);
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        match parse_line(trimmed) {
+            Some(param) => params.push(param),
+            None => {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          format!("invalid parameter line: {:?}", trimmed)))
+            }
+        }
+    }
+    Ok(params)
+}
+
+pub fn save<P: AsRef<Path>>(path: P,
+                            params: &[(String, ParamValue)],
+                            format: ParamFileFormat)
+                            -> io::Result<()> {
+    let mut file = try!(File::create(path));
+    for &(ref name, ref value) in params {
+        match format {
+            ParamFileFormat::MissionPlanner => try!(writeln!(file, "{},{}", name, value)),
+            ParamFileFormat::MAVProxy => try!(writeln!(file, "{:<16} {}", name, value)),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parse_line() {
+    assert_eq!(parse_line("FS_GCS_ENABLE,1"), Some(("FS_GCS_ENABLE".into(), 1.0)));
+    assert_eq!(parse_line("RTL_ALT          1500 # cm"), Some(("RTL_ALT".into(), 1500.0)));
+    assert_eq!(parse_line("SERIAL1_OPTIONS,16777217"),
+               Some(("SERIAL1_OPTIONS".into(), 16777217.0)));
+    assert_eq!(parse_line("ARMING_CHECK"), None);
+}
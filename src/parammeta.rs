@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+
+use xml::reader::{EventReader, XmlEvent};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParamMetadata {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub units: Option<String>,
+    pub range: Option<(f32, f32)>,
+    pub increment: Option<f32>,
+    pub values: Vec<(f32, String)>,
+    pub bitmask: Vec<(u8, String)>,
+    pub reboot_required: bool,
+}
+
+impl ParamMetadata {
+    fn new(name: &str) -> ParamMetadata {
+        ParamMetadata {
+            name: name.into(),
+            display_name: None,
+            description: None,
+            units: None,
+            range: None,
+            increment: None,
+            values: vec![],
+            bitmask: vec![],
+            reboot_required: false,
+        }
+    }
+
+    fn set_field(&mut self, field: &str, text: &str) {
+        match field {
+            "Range" => {
+                let bounds: Vec<f32> = text.split_whitespace()
+                                           .filter_map(|x| x.parse::<f32>().ok())
+                                           .collect();
+                if bounds.len() == 2 {
+                    self.range = Some((bounds[0], bounds[1]));
+                }
+            }
+            "Increment" => {
+                self.increment = text.trim().parse::<f32>().ok();
+            }
+            "Units" => {
+                self.units = Some(text.trim().into());
+            }
+            "RebootRequired" => {
+                self.reboot_required = text.trim() == "True";
+            }
+            "Bitmask" => {
+                self.bitmask = text.split(',')
+                                   .filter_map(|bit| {
+                                       let mut parts = bit.splitn(2, ':');
+                                       match (parts.next(), parts.next()) {
+                                           (Some(index), Some(label)) => {
+                                               index.trim()
+                                                    .parse::<u8>()
+                                                    .ok()
+                                                    .map(|x| (x, label.trim().into()))
+                                           }
+                                           _ => None,
+                                       }
+                                   })
+                                   .collect();
+            }
+            _ => (),
+        }
+    }
+
+    // Checks a value against the documented range.
+    pub fn validate(&self, value: f32) -> Result<(), String> {
+        if let Some((min, max)) = self.range {
+            if value < min || value > max {
+                return Err(format!("{} must be between {} and {}, got {}",
+                                   self.name,
+                                   min,
+                                   max,
+                                   value));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ParamMetadataDb {
+    params: HashMap<String, ParamMetadata>,
+}
+
+impl ParamMetadataDb {
+    // Loads an ArduPilot `apm.pdef.xml` parameter definition file. Vehicle
+    // prefixes such as `ArduCopter:` are stripped from parameter names.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<ParamMetadataDb> {
+        let file = BufReader::new(try!(File::open(path)));
+
+        let mut params = HashMap::new();
+        let mut param: Option<ParamMetadata> = None;
+        let mut field: Option<String> = None;
+        let mut code: Option<f32> = None;
+
+        for e in EventReader::new(file) {
+            match e {
+                Ok(XmlEvent::StartElement { name, attributes: attrs, .. }) => {
+                    let attr = |key: &str| {
+                        attrs.iter()
+                             .find(|x| x.name.local_name == key)
+                             .map(|x| x.value.clone())
+                    };
+                    match name.local_name.as_ref() {
+                        "param" => {
+                            param = attr("name").map(|name| {
+                                let name = name.rsplit(':').next().unwrap_or("").to_string();
+                                let mut meta = ParamMetadata::new(&name);
+                                meta.display_name = attr("humanName");
+                                meta.description = attr("documentation");
+                                meta
+                            });
+                        }
+                        "field" => {
+                            field = attr("name");
+                        }
+                        "value" => {
+                            code = attr("code").and_then(|x| x.parse::<f32>().ok());
+                        }
+                        _ => (),
+                    }
+                }
+                Ok(XmlEvent::Characters(text)) => {
+                    if let Some(ref mut meta) = param {
+                        if let Some(ref field) = field {
+                            meta.set_field(field, &text);
+                        } else if let Some(code) = code {
+                            meta.values.push((code, text.trim().into()));
+                        }
+                    }
+                }
+                Ok(XmlEvent::EndElement { name }) => {
+                    match name.local_name.as_ref() {
+                        "param" => {
+                            if let Some(meta) = param.take() {
+                                params.insert(meta.name.clone(), meta);
+                            }
+                        }
+                        "field" => {
+                            field = None;
+                        }
+                        "value" => {
+                            code = None;
+                        }
+                        _ => (),
+                    }
+                }
+                Err(e) => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{}", e)));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(ParamMetadataDb { params: params })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ParamMetadata> {
+        self.params.get(name)
+    }
+
+    // Parameters without metadata are always accepted.
+    pub fn validate(&self, name: &str, value: f32) -> Result<(), String> {
+        match self.params.get(name) {
+            Some(meta) => meta.validate(value),
+            None => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn test_load_metadata() {
+    use std::env;
+    use std::io::Write;
+
+    let path = env::temp_dir().join(format!("dronekit-pdef-{}.xml", ::std::process::id()));
+    File::create(&path)
+        .unwrap()
+        .write_all(br#"<?xml version="1.0" encoding="utf-8"?>
+<paramfile>
+<vehicles>
+<parameters name="ArduCopter">
+<param humanName="Fence Type" name="ArduCopter:FENCE_TYPE" documentation="Enabled fence types">
+<field name="Bitmask">0:Altitude,1:Circle, 2:Polygon</field>
+</param>
+<param humanName="Fence Action" name="ArduCopter:FENCE_ACTION" documentation="What to do on breach">
+<values>
+<value code="0">Report Only</value>
+<value code="1">RTL or Land</value>
+</values>
+</param>
+<param humanName="Fence Maximum Altitude" name="ArduCopter:FENCE_ALT_MAX" documentation="Maximum altitude">
+<field name="Range">10 1000</field>
+<field name="Increment">1</field>
+<field name="Units">m</field>
+</param>
+<param humanName="Frame Class" name="ArduCopter:FRAME_CLASS" documentation="Frame class">
+<field name="RebootRequired">True</field>
+</param>
+</parameters>
+</vehicles>
+</paramfile>
+"#)
+        .unwrap();
+    let db = ParamMetadataDb::load(&path).unwrap();
+    let _ = ::std::fs::remove_file(&path);
+
+    let fence_type = db.get("FENCE_TYPE").unwrap();
+    assert_eq!(fence_type.display_name, Some("Fence Type".into()));
+    assert_eq!(fence_type.bitmask,
+               vec![(0, "Altitude".into()), (1, "Circle".into()), (2, "Polygon".into())]);
+    assert!(!fence_type.reboot_required);
+
+    assert_eq!(db.get("FENCE_ACTION").unwrap().values,
+               vec![(0.0, "Report Only".into()), (1.0, "RTL or Land".into())]);
+
+    let alt_max = db.get("FENCE_ALT_MAX").unwrap();
+    assert_eq!(alt_max.range, Some((10.0, 1000.0)));
+    assert_eq!(alt_max.increment, Some(1.0));
+    assert_eq!(alt_max.units, Some("m".into()));
+
+    assert!(db.get("FRAME_CLASS").unwrap().reboot_required);
+
+    assert!(db.validate("FENCE_ALT_MAX", 100.0).is_ok());
+    assert!(db.validate("FENCE_ALT_MAX", 5.0).is_err());
+    assert!(db.validate("FENCE_ALT_MAX", 1000.5).is_err());
+    assert!(db.validate("UNKNOWN", 1e9).is_ok());
+}
@@ -0,0 +1,206 @@
+// ArduPilot's point protocols for fences and rally points, used with
+// dialects whose mission protocol has no mission type. Neither protocol is
+// acknowledged: the number of points is a parameter (FENCE_TOTAL or
+// RALLY_TOTAL), points are written one at a time, and each is read back with
+// a fetch message to confirm it. Requests are resent after a quiet period.
+
+use mavlink::*;
+
+use eventual::Future;
+use time;
+
+use connection::{Outbox, VehicleConnection, parse_mavlink_string};
+use param::{ParamEncoding, ParamValue};
+use vehicle::{send_param_read, send_param_set};
+
+// How long to wait for a reply before resending a request, and how many
+// times a request is sent before giving up.
+const POINT_QUIET_NS: u64 = 1_000_000_000;
+const POINT_ATTEMPTS: usize = 3;
+
+// The messages and parameter of one point protocol.
+pub struct PointProtocol<P> {
+    // The parameter holding the number of points.
+    pub total_param: &'static str,
+    // Builds the message writing point `index` of `count`.
+    pub encode: fn(&Outbox, u8, u8, &P) -> DkMessage,
+    // Builds the message requesting point `index`.
+    pub fetch: fn(&Outbox, u8) -> DkMessage,
+    // Returns the index and point carried by a message, if it is a point.
+    pub decode: fn(&DkMessage) -> Option<(u8, P)>,
+    // Whether a point read back matches the one written, allowing for the
+    // autopilot's storage precision.
+    pub matches: fn(&P, &P) -> bool,
+}
+
+// Reads the point count and then each point, resolving to `convert` of the
+// points read. Fails if the vehicle stops replying or `convert` returns
+// `None`.
+pub fn download<P, T, F>(conn: &mut VehicleConnection,
+                         protocol: PointProtocol<P>,
+                         encoding: ParamEncoding,
+                         convert: F)
+                         -> Future<T, ()>
+    where P: Send + 'static,
+          T: Send + 'static,
+          F: FnOnce(Vec<P>) -> Option<T> + Send + 'static
+{
+    let (tx, future) = Future::<T, ()>::pair();
+
+    let outbox = conn.outbox();
+    let mut tx = Some(tx);
+    let mut convert = Some(convert);
+    let mut total = None;
+    let mut points = vec![];
+    let mut attempts = 1;
+    let mut last_sent = time::precise_time_ns();
+    conn.watch(Box::new(move |msg| {
+        let now = time::precise_time_ns();
+        match msg {
+            DkMessage::PARAM_VALUE(ref data) if total.is_none() &&
+                                                parse_mavlink_string(&data.param_id) ==
+                                                protocol.total_param => {
+                let value = ParamValue::decode(data.param_value, data.param_type, encoding);
+                total = Some(value.as_f32() as usize);
+            }
+            ref msg => {
+                let expected = points.len();
+                match (protocol.decode)(msg).filter(|&(index, _)| {
+                    total.is_some() && index as usize == expected
+                }) {
+                    Some((_, point)) => points.push(point),
+                    _ => {
+                        if now - last_sent < POINT_QUIET_NS {
+                            return false;
+                        }
+                        if attempts >= POINT_ATTEMPTS {
+                            if let Some(tx) = tx.take() {
+                                tx.fail(());
+                            }
+                            return true;
+                        }
+                        attempts += 1;
+                        last_sent = now;
+                        match total {
+                            None => send_param_read(&outbox, protocol.total_param),
+                            Some(_) => outbox.send((protocol.fetch)(&outbox, points.len() as u8)),
+                        }
+                        return false;
+                    }
+                }
+            }
+        }
+
+        if points.len() < total.unwrap_or(0) {
+            attempts = 1;
+            last_sent = now;
+            outbox.send((protocol.fetch)(&outbox, points.len() as u8));
+            return false;
+        }
+
+        let result = convert.take().and_then(|convert| convert(points.drain(..).collect()));
+        if let Some(tx) = tx.take() {
+            match result {
+                Some(result) => tx.complete(result),
+                None => tx.fail(()),
+            }
+        }
+        true
+    }));
+
+    send_param_read(&conn.outbox(), protocol.total_param);
+
+    future
+}
+
+fn send_point<P>(outbox: &Outbox, protocol: &PointProtocol<P>, points: &[P], index: u8) {
+    outbox.send((protocol.encode)(outbox, index, points.len() as u8, &points[index as usize]));
+    outbox.send((protocol.fetch)(outbox, index));
+}
+
+// Sets the point count to `total`, a value of the count parameter's type,
+// and then writes each point, reading it back before moving on. Points that
+// read back differently are rewritten. Fails if the count is rejected or the
+// vehicle stops confirming.
+pub fn upload<P>(conn: &mut VehicleConnection,
+                 protocol: PointProtocol<P>,
+                 encoding: ParamEncoding,
+                 total: ParamValue,
+                 points: Vec<P>)
+                 -> Future<(), ()>
+    where P: Send + 'static
+{
+    let (tx, future) = Future::<(), ()>::pair();
+    if points.len() > u8::MAX as usize || total.as_f32() as usize != points.len() {
+        tx.fail(());
+        return future;
+    }
+
+    let outbox = conn.outbox();
+    let total_param = protocol.total_param;
+    let mut tx = Some(tx);
+    let mut counted = false;
+    let mut next = 0;
+    let mut attempts = 1;
+    let mut last_sent = time::precise_time_ns();
+    conn.watch(Box::new(move |msg| {
+        let now = time::precise_time_ns();
+        match msg {
+            DkMessage::PARAM_VALUE(ref data) if !counted &&
+                                                parse_mavlink_string(&data.param_id) ==
+                                                protocol.total_param => {
+                let value = ParamValue::decode(data.param_value, data.param_type, encoding);
+                if value.as_f32() != total.as_f32() {
+                    if let Some(tx) = tx.take() {
+                        tx.fail(());
+                    }
+                    return true;
+                }
+                counted = true;
+            }
+            ref msg => {
+                match (protocol.decode)(msg) {
+                    Some((index, ref point)) if counted && index as usize == next &&
+                                                (protocol.matches)(point, &points[next]) => {
+                        next += 1;
+                    }
+                    _ => {
+                        if now - last_sent < POINT_QUIET_NS {
+                            return false;
+                        }
+                        if attempts >= POINT_ATTEMPTS {
+                            if let Some(tx) = tx.take() {
+                                tx.fail(());
+                            }
+                            return true;
+                        }
+                        attempts += 1;
+                        last_sent = now;
+                        if counted {
+                            send_point(&outbox, &protocol, &points, next as u8);
+                        } else {
+                            send_param_set(&outbox, protocol.total_param, total, encoding);
+                        }
+                        return false;
+                    }
+                }
+            }
+        }
+
+        if next < points.len() {
+            attempts = 1;
+            last_sent = now;
+            send_point(&outbox, &protocol, &points, next as u8);
+            return false;
+        }
+
+        if let Some(tx) = tx.take() {
+            tx.complete(());
+        }
+        true
+    }));
+
+    send_param_set(&conn.outbox(), total_param, total, encoding);
+
+    future
+}
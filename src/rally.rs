@@ -0,0 +1,224 @@
+// Rally points, alternate places to return to and land. This dialect's
+// mission protocol has no mission type, so rally points are transferred with
+// ArduPilot's RALLY_POINT protocol (see `points`). Their altitudes are
+// relative to home.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use mavlink::*;
+
+use connection::Outbox;
+use mission::MissionItem;
+use points::PointProtocol;
+use vehicle::LocationGlobalRelative;
+
+const MAV_CMD_NAV_RALLY_POINT: u16 = 5100;
+
+const MAV_FRAME_GLOBAL_RELATIVE_ALT: u8 = 3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RallyPoint {
+    pub location: LocationGlobalRelative,
+    // Altitude in meters above home to break off a loiter and head for the
+    // point.
+    pub break_alt: i16,
+    // Heading to land on, in centidegrees.
+    pub land_dir: u16,
+    pub flags: u8,
+}
+
+impl RallyPoint {
+    pub fn new(location: LocationGlobalRelative) -> RallyPoint {
+        RallyPoint {
+            location: location,
+            break_alt: 0,
+            land_dir: 0,
+            flags: 0,
+        }
+    }
+
+    // Encodes the point as a rally mission item. MAV_CMD_NAV_RALLY_POINT
+    // defines no parameters, so `break_alt`, `land_dir` and `flags` are kept
+    // in params 1 to 3, which autopilots ignore, to survive a round trip.
+    pub fn to_item(&self) -> MissionItem {
+        MissionItem {
+            command: MAV_CMD_NAV_RALLY_POINT,
+            frame: MAV_FRAME_GLOBAL_RELATIVE_ALT,
+            param1: self.break_alt as f32,
+            param2: self.land_dir as f32,
+            param3: self.flags as f32,
+            param4: 0.0,
+            x: (self.location.lat as f64 / 1e7) as f32,
+            y: (self.location.lon as f64 / 1e7) as f32,
+            z: self.location.alt as f32 / 1000.0,
+            autocontinue: 0,
+        }
+    }
+
+    pub fn from_item(item: &MissionItem) -> Option<RallyPoint> {
+        if item.command != MAV_CMD_NAV_RALLY_POINT || item.frame != MAV_FRAME_GLOBAL_RELATIVE_ALT {
+            return None;
+        }
+        Some(RallyPoint {
+            location: LocationGlobalRelative {
+                lat: (item.x as f64 * 1e7).round() as i32,
+                lon: (item.y as f64 * 1e7).round() as i32,
+                alt: (item.z as f64 * 1000.0).round() as i32,
+            },
+            break_alt: item.param1 as i16,
+            land_dir: item.param2 as u16,
+            flags: item.param3 as u8,
+        })
+    }
+
+    // Parses a line of a MAVProxy rally file:
+    // RALLY <lat> <lon> <alt> <break_alt> <land_dir> <flags>
+    // with altitudes in meters above home and `land_dir` in degrees, as
+    // MAVProxy's rally module loads it.
+    fn parse_line(line: &str) -> Option<RallyPoint> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 7 || !fields[0].eq_ignore_ascii_case("RALLY") {
+            return None;
+        }
+        let lat = fields[1].parse::<f64>().ok();
+        let lon = fields[2].parse::<f64>().ok();
+        let alt = fields[3].parse::<f64>().ok();
+        let break_alt = fields[4].parse::<f64>().ok();
+        let land_dir = fields[5].parse::<f64>().ok();
+        let flags = fields[6].parse::<u8>().ok();
+        match (lat, lon, alt, break_alt, land_dir, flags) {
+            (Some(lat), Some(lon), Some(alt), Some(break_alt), Some(land_dir), Some(flags)) => {
+                Some(RallyPoint {
+                    location: LocationGlobalRelative {
+                        lat: (lat * 1e7).round() as i32,
+                        lon: (lon * 1e7).round() as i32,
+                        alt: (alt * 1000.0).round() as i32,
+                    },
+                    break_alt: break_alt.round() as i16,
+                    land_dir: (land_dir * 100.0).round() as u16,
+                    flags: flags,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn format_line(&self) -> String {
+        format!("RALLY\t{:.7}\t{:.7}\t{:.2}\t{}\t{:.2}\t{}",
+                self.location.lat as f64 / 1e7,
+                self.location.lon as f64 / 1e7,
+                self.location.alt as f64 / 1000.0,
+                self.break_alt,
+                self.land_dir as f64 / 100.0,
+                self.flags)
+    }
+}
+
+// RALLY_POINT holds whole meters.
+fn alt_meters(location: &LocationGlobalRelative) -> i16 {
+    (location.alt as f64 / 1000.0).round() as i16
+}
+
+fn same_point(a: &RallyPoint, b: &RallyPoint) -> bool {
+    a.location.lat == b.location.lat && a.location.lon == b.location.lon &&
+    alt_meters(&a.location) == alt_meters(&b.location) && a.break_alt == b.break_alt &&
+    a.land_dir == b.land_dir && a.flags == b.flags
+}
+
+fn encode_point(outbox: &Outbox, index: u8, count: u8, point: &RallyPoint) -> DkMessage {
+    DkMessage::RALLY_POINT(RALLY_POINT_DATA {
+        lat: point.location.lat,
+        lng: point.location.lon,
+        alt: alt_meters(&point.location),
+        break_alt: point.break_alt,
+        land_dir: point.land_dir,
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+        idx: index,
+        count: count,
+        flags: point.flags,
+    })
+}
+
+fn fetch_point(outbox: &Outbox, index: u8) -> DkMessage {
+    DkMessage::RALLY_FETCH_POINT(RALLY_FETCH_POINT_DATA {
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+        idx: index,
+    })
+}
+
+fn decode_point(msg: &DkMessage) -> Option<(u8, RallyPoint)> {
+    match *msg {
+        DkMessage::RALLY_POINT(ref data) => {
+            Some((data.idx,
+                  RallyPoint {
+                location: LocationGlobalRelative {
+                    lat: data.lat,
+                    lon: data.lng,
+                    alt: data.alt as i32 * 1000,
+                },
+                break_alt: data.break_alt,
+                land_dir: data.land_dir,
+                flags: data.flags,
+            }))
+        }
+        _ => None,
+    }
+}
+
+pub fn rally_protocol() -> PointProtocol<RallyPoint> {
+    PointProtocol {
+        total_param: "RALLY_TOTAL",
+        encode: encode_point,
+        fetch: fetch_point,
+        decode: decode_point,
+        matches: same_point,
+    }
+}
+
+pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Vec<RallyPoint>> {
+    let file = BufReader::new(try!(File::open(path)));
+    let mut points = vec![];
+    for line in file.lines() {
+        let line = try!(line);
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match RallyPoint::parse_line(line) {
+            Some(point) => points.push(point),
+            None => {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          format!("invalid rally point: {:?}", line)))
+            }
+        }
+    }
+    Ok(points)
+}
+
+pub fn save<P: AsRef<Path>>(path: P, points: &[RallyPoint]) -> io::Result<()> {
+    let mut file = try!(File::create(path));
+    for point in points {
+        try!(writeln!(file, "{}", point.format_line()));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rally_line() {
+    let point = RallyPoint::parse_line("RALLY\t-35.3632620\t149.1652370\t30.00\t40\t90.00\t1")
+                    .unwrap();
+    assert_eq!(point.location,
+               LocationGlobalRelative {
+                   lat: -353632620,
+                   lon: 1491652370,
+                   alt: 30000,
+               });
+    assert_eq!(point.land_dir, 9000);
+    assert_eq!(RallyPoint::parse_line(&point.format_line()), Some(point.clone()));
+    let from_item = RallyPoint::from_item(&point.to_item()).unwrap();
+    assert_eq!((from_item.break_alt, from_item.land_dir, from_item.flags), (40, 9000, 1));
+}
@@ -0,0 +1,122 @@
+// Telemetry values tracked by `Vehicle`, in SI units unless noted.
+
+use std::default::Default;
+
+use vehicle::{LocationGlobal, LocationGlobalRelative, LocationLocal};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Attitude {
+    // Radians.
+    pub roll: f32,
+    pub pitch: f32,
+    pub yaw: f32,
+    // Radians per second.
+    pub rollspeed: f32,
+    pub pitchspeed: f32,
+    pub yawspeed: f32,
+}
+
+// North, east and down velocity in meters per second.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Velocity {
+    pub vx: f32,
+    pub vy: f32,
+    pub vz: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GpsInfo {
+    // GPS_FIX_TYPE: 0-1 no fix, 2 2D fix, 3 3D fix and above.
+    pub fix_type: u8,
+    pub satellites_visible: u8,
+    // Dilution of precision, or `None` if unknown.
+    pub eph: Option<f32>,
+    pub epv: Option<f32>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Battery {
+    // Volts.
+    pub voltage: f32,
+    // Amps, or `None` if not measured.
+    pub current: Option<f32>,
+    // Percent remaining, or `None` if not estimated.
+    pub level: Option<u8>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rangefinder {
+    // Meters.
+    pub distance: f32,
+    pub min_distance: f32,
+    pub max_distance: f32,
+}
+
+// MAV_STATE, as reported in HEARTBEAT.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SystemStatus {
+    Uninit,
+    Boot,
+    Calibrating,
+    Standby,
+    Active,
+    Critical,
+    Emergency,
+    Poweroff,
+    Unknown(u8),
+}
+
+impl SystemStatus {
+    pub fn from_mav_state(state: u8) -> SystemStatus {
+        match state {
+            0 => SystemStatus::Uninit,
+            1 => SystemStatus::Boot,
+            2 => SystemStatus::Calibrating,
+            3 => SystemStatus::Standby,
+            4 => SystemStatus::Active,
+            5 => SystemStatus::Critical,
+            6 => SystemStatus::Emergency,
+            7 => SystemStatus::Poweroff,
+            x => SystemStatus::Unknown(x),
+        }
+    }
+}
+
+// The value of a `Vehicle` attribute, as passed to attribute listeners.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttributeValue {
+    LocationGlobal(LocationGlobal),
+    LocationGlobalRelative(LocationGlobalRelative),
+    LocationLocal(LocationLocal),
+    Attitude(Attitude),
+    Velocity(Velocity),
+    Heading(i16),
+    Speed(f32),
+    Gps(GpsInfo),
+    Battery(Battery),
+    SystemStatus(SystemStatus),
+    Flag(bool),
+    Mode(u32),
+    Rangefinder(Rangefinder),
+    // Seconds since the last heartbeat.
+    Heartbeat(f64),
+}
+
+// Controls when an attribute listener fires. By default it fires on every
+// update.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ListenerOptions {
+    // Only fire when the value differs from the last one delivered.
+    pub changes_only: bool,
+    // Maximum number of calls per second.
+    pub max_rate: Option<f32>,
+}
+
+impl Default for ListenerOptions {
+    fn default() -> ListenerOptions {
+        ListenerOptions {
+            changes_only: false,
+            max_rate: None,
+        }
+    }
+}
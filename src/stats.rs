@@ -0,0 +1,183 @@
+// Link quality counters, kept by the event loop for each connection.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use time;
+
+use connection::MavHeader;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SourceStats {
+    pub received: u64,
+    // Frames missing from gaps in the sequence numbers.
+    pub lost: u64,
+    last_sequence: Option<u8>,
+}
+
+impl SourceStats {
+    fn record(&mut self, sequence: u8) {
+        if let Some(last) = self.last_sequence {
+            self.lost += sequence.wrapping_sub(last).wrapping_sub(1) as u64;
+        }
+        self.last_sequence = Some(sequence);
+        self.received += 1;
+    }
+
+    // Fraction of frames lost, from 0 to 1.
+    pub fn loss(&self) -> f32 {
+        if self.received + self.lost == 0 {
+            0.0
+        } else {
+            self.lost as f32 / (self.received + self.lost) as f32
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LinkStats {
+    // Monotonic time in nanoseconds (see `time::precise_time_ns`) at which
+    // this snapshot was taken.
+    pub time: u64,
+    pub received: u64,
+    pub sent: u64,
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+    pub crc_errors: u64,
+    // Frames with ids this dialect doesn't define, whose checksums can't be
+    // verified without their CRC_EXTRA.
+    pub unknown_ids: u64,
+    // By sender system and component id.
+    pub sources: BTreeMap<(u8, u8), SourceStats>,
+    // Frames received by message id.
+    pub messages: BTreeMap<u8, u64>,
+}
+
+// Rates between two snapshots of `LinkStats`, per second.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkRates {
+    pub received: f32,
+    pub sent: f32,
+    pub bytes_received: f32,
+    pub bytes_sent: f32,
+    pub messages: BTreeMap<u8, f32>,
+}
+
+impl LinkStats {
+    pub fn new() -> LinkStats {
+        LinkStats {
+            time: time::precise_time_ns(),
+            received: 0,
+            sent: 0,
+            bytes_received: 0,
+            bytes_sent: 0,
+            crc_errors: 0,
+            unknown_ids: 0,
+            sources: BTreeMap::new(),
+            messages: BTreeMap::new(),
+        }
+    }
+
+    pub fn record_frame(&mut self, header: &MavHeader, message_id: u8) {
+        self.received += 1;
+        *self.messages.entry(message_id).or_insert(0) += 1;
+        self.sources
+            .entry((header.system_id, header.component_id))
+            .or_insert_with(SourceStats::default)
+            .record(header.sequence);
+    }
+
+    pub fn record_sent(&mut self, bytes: usize) {
+        self.sent += 1;
+        self.bytes_sent += bytes as u64;
+    }
+
+    // Fraction of frames lost across all senders, from 0 to 1.
+    pub fn loss(&self) -> f32 {
+        let lost: u64 = self.sources.values().map(|x| x.lost).sum();
+        let received: u64 = self.sources.values().map(|x| x.received).sum();
+        if received + lost == 0 {
+            0.0
+        } else {
+            lost as f32 / (received + lost) as f32
+        }
+    }
+
+    // Returns a copy with `time` set to now.
+    pub fn snapshot(&self) -> LinkStats {
+        let mut stats = self.clone();
+        stats.time = time::precise_time_ns();
+        stats
+    }
+
+    // Average rates since `earlier`, a previous snapshot of the same link.
+    // Pass `LinkStats::new()` from when the link opened for lifetime rates.
+    pub fn rates_since(&self, earlier: &LinkStats) -> LinkRates {
+        let seconds = (self.time.saturating_sub(earlier.time)) as f32 / 1e9;
+        let rate = |now: u64, then: u64| {
+            if seconds > 0.0 {
+                now.saturating_sub(then) as f32 / seconds
+            } else {
+                0.0
+            }
+        };
+        LinkRates {
+            received: rate(self.received, earlier.received),
+            sent: rate(self.sent, earlier.sent),
+            bytes_received: rate(self.bytes_received, earlier.bytes_received),
+            bytes_sent: rate(self.bytes_sent, earlier.bytes_sent),
+            messages: self.messages
+                          .iter()
+                          .map(|(&id, &count)| {
+                              (id, rate(count, earlier.messages.get(&id).cloned().unwrap_or(0)))
+                          })
+                          .collect(),
+        }
+    }
+}
+
+impl fmt::Display for LinkStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(writeln!(f,
+                      "received {} frames ({} bytes), sent {} frames ({} bytes)",
+                      self.received,
+                      self.bytes_received,
+                      self.sent,
+                      self.bytes_sent));
+        try!(writeln!(f,
+                      "crc errors {}, unknown ids {}, loss {:.1}%",
+                      self.crc_errors,
+                      self.unknown_ids,
+                      self.loss() * 100.0));
+        for (&(system_id, component_id), source) in &self.sources {
+            try!(writeln!(f,
+                          "  {}:{} received {}, lost {} ({:.1}%)",
+                          system_id,
+                          component_id,
+                          source.received,
+                          source.lost,
+                          source.loss() * 100.0));
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_sequence_loss() {
+    let header = |sequence| {
+        MavHeader {
+            system_id: 1,
+            component_id: 1,
+            sequence: sequence,
+        }
+    };
+
+    let mut stats = LinkStats::new();
+    for &sequence in &[250, 251, 254, 255, 0, 3] {
+        stats.record_frame(&header(sequence), 0);
+    }
+    let source = &stats.sources[&(1, 1)];
+    assert_eq!(source.received, 6);
+    assert_eq!(source.lost, 4);
+    assert_eq!(stats.messages[&0], 6);
+}
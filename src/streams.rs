@@ -0,0 +1,84 @@
+// Telemetry rate configuration. ArduPilot groups messages into streams that
+// are set with REQUEST_DATA_STREAM; newer autopilots also accept per-message
+// intervals (MAV_CMD_SET_MESSAGE_INTERVAL).
+
+// MAV_DATA_STREAM groups.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StreamGroup {
+    All,
+    RawSensors,
+    ExtendedStatus,
+    RcChannels,
+    RawController,
+    Position,
+    Extra1,
+    Extra2,
+    Extra3,
+}
+
+impl StreamGroup {
+    pub fn stream_id(&self) -> u8 {
+        match *self {
+            StreamGroup::All => 0,
+            StreamGroup::RawSensors => 1,
+            StreamGroup::ExtendedStatus => 2,
+            StreamGroup::RcChannels => 3,
+            StreamGroup::RawController => 4,
+            StreamGroup::Position => 6,
+            StreamGroup::Extra1 => 10,
+            StreamGroup::Extra2 => 11,
+            StreamGroup::Extra3 => 12,
+        }
+    }
+}
+
+// Interval values with special meaning to MAV_CMD_SET_MESSAGE_INTERVAL.
+pub const INTERVAL_DISABLED: i32 = -1;
+pub const INTERVAL_DEFAULT: i32 = 0;
+
+// Converts a rate in Hz to a message interval in microseconds. Rates of zero
+// or less disable the message.
+pub fn interval_us(hz: f32) -> i32 {
+    if hz <= 0.0 {
+        INTERVAL_DISABLED
+    } else {
+        (1e6 / hz).round() as i32
+    }
+}
+
+// The telemetry rates an application needs, applied with
+// `Vehicle::apply_stream_profile`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StreamProfile {
+    // Stream groups and rates in Hz, requested first. A rate of 0 stops the
+    // group.
+    pub groups: Vec<(StreamGroup, u16)>,
+    // Message ids and rates in Hz, overriding the groups.
+    pub messages: Vec<(u8, f32)>,
+}
+
+impl StreamProfile {
+    pub fn new() -> StreamProfile {
+        StreamProfile::default()
+    }
+
+    // Position and attitude at `hz`, status at 2 Hz, and nothing else; enough
+    // for `Vehicle`'s attributes.
+    pub fn navigation(hz: f32) -> StreamProfile {
+        StreamProfile {
+            groups: vec![(StreamGroup::All, 0),
+                         (StreamGroup::ExtendedStatus, 2),
+                         (StreamGroup::Position, hz as u16),
+                         (StreamGroup::Extra1, hz as u16),
+                         (StreamGroup::Extra2, hz as u16)],
+            messages: vec![],
+        }
+    }
+}
+
+#[test]
+fn test_interval_us() {
+    assert_eq!(interval_us(10.0), 100_000);
+    assert_eq!(interval_us(3.0), 333_333);
+    assert_eq!(interval_us(0.0), INTERVAL_DISABLED);
+}
@@ -0,0 +1,67 @@
+// Telemetry logs as written by MAVProxy and Mission Planner: each frame is
+// preceded by a big-endian timestamp in microseconds since the Unix epoch.
+
+use std::io::{self, Read};
+
+use connection::{MavFrame, read_frames_with_stats};
+use stats::LinkStats;
+
+pub struct TlogReader<R> {
+    reader: R,
+    stats: LinkStats,
+}
+
+impl<R: Read> TlogReader<R> {
+    pub fn new(reader: R) -> TlogReader<R> {
+        TlogReader {
+            reader: reader,
+            stats: LinkStats::new(),
+        }
+    }
+
+    // Counts of the frames read so far. Times are those of reading, not of
+    // the log.
+    pub fn stats(&self) -> &LinkStats {
+        &self.stats
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<(u64, Vec<u8>)>> {
+        let mut timestamp = [0; 8];
+        match self.reader.read_exact(&mut timestamp) {
+            Ok(()) => {}
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+        let timestamp = timestamp.iter().fold(0, |acc, &b| (acc << 8) | b as u64);
+        let mut start = [0; 2];
+        try!(self.reader.read_exact(&mut start));
+        if start[0] != 0xfe {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "expected a MAVLink frame"));
+        }
+        let mut frame = vec![0; 8 + start[1] as usize];
+        frame[0] = start[0];
+        frame[1] = start[1];
+        try!(self.reader.read_exact(&mut frame[2..]));
+        Ok(Some((timestamp, frame)))
+    }
+}
+
+// Yields each timestamp and frame, skipping frames with a bad checksum. Stops
+// at the end of the log or the first malformed record.
+impl<R: Read> Iterator for TlogReader<R> {
+    type Item = (u64, MavFrame);
+
+    fn next(&mut self) -> Option<(u64, MavFrame)> {
+        loop {
+            match self.read_record() {
+                Ok(Some((timestamp, mut bytes))) => {
+                    self.stats.bytes_received += bytes.len() as u64;
+                    if let Some(frame) = read_frames_with_stats(&mut bytes, &mut self.stats).pop() {
+                        return Some((timestamp, frame));
+                    }
+                }
+                Ok(None) | Err(..) => return None,
+            }
+        }
+    }
+}
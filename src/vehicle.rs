@@ -3,34 +3,275 @@ extern crate bit_vec;
 
 use mavlink::*;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io;
 use std::iter::repeat;
+use std::path::Path;
 use std::cell::RefCell;
 use std::rc::Rc;
-use eventual::Future;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+use eventual::{Async, Complete, Future};
 use bit_vec::BitVec;
+use time;
+
+use connection::{VehicleConnection, LinkEvent, MavHeader, Outbox, Subscription, MAV_TYPE_GCS,
+                 parse_mavlink_string};
+use stats::LinkStats;
+use streams::{StreamGroup, StreamProfile, interval_us};
+use guided::{AltitudeFrame, GlobalTarget, VelocityFrame, VelocityStream, position_target,
+             velocity_target};
+use fence::{Fence, fence_protocol};
+use mission::Mission;
+use points;
+use rally::{RallyPoint, rally_protocol};
+use state::{Attitude, AttributeValue, Battery, GpsInfo, ListenerOptions, Rangefinder,
+            SystemStatus, Velocity};
+use param::{ParamChangeEvent, ParamEncoding, ParamError, ParamValue};
+use paramfile::{ApplySummary, ParamChange};
+use parammeta::{ParamMetadata, ParamMetadataDb};
+
+// How long the parameter stream must be silent before missing indexes are
+// re-requested, and how many are requested at once.
+const PARAM_QUIET_NS: u64 = 1_000_000_000;
+const PARAM_RETRY_BATCH: usize = 10;
+
+// How many times a PARAM_SET is sent before giving up on a reply.
+const PARAM_SET_ATTEMPTS: usize = 3;
+
+const MAV_AUTOPILOT_INVALID: u8 = 8;
+
+const MAV_SYS_STATUS_AHRS: u32 = 0x200000;
+
+const MAV_RESULT_ACCEPTED: u8 = 0;
+
+// How long to wait for MAV_CMD_SET_MESSAGE_INTERVAL to be acknowledged, or
+// for MAV_CMD_GET_MESSAGE_INTERVAL to be answered, and how many times the
+// latter is sent before giving up.
+const INTERVAL_ACK_TIMEOUT_NS: u64 = 3_000_000_000;
+const INTERVAL_GET_ATTEMPTS: usize = 3;
+
+// The ArduPilot firmwares, which number their flight modes differently.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Firmware {
+    Copter,
+    Plane,
+    Rover,
+}
+
+impl Firmware {
+    // Picks the firmware from the MAV_TYPE reported in HEARTBEAT.
+    pub fn from_mav_type(mav_type: u8) -> Option<Firmware> {
+        match mav_type {
+            2 | 3 | 4 | 13 | 14 | 15 => Some(Firmware::Copter),
+            1 | 19..=25 => Some(Firmware::Plane),
+            10 | 11 => Some(Firmware::Rover),
+            _ => None,
+        }
+    }
 
-use connection::{VehicleConnection, parse_mavlink_string};
+    // Each mode this firmware supports and its HEARTBEAT custom_mode.
+    fn modes(&self) -> &'static [(VehicleMode, u32)] {
+        match *self {
+            Firmware::Copter => COPTER_MODES,
+            Firmware::Plane => PLANE_MODES,
+            Firmware::Rover => ROVER_MODES,
+        }
+    }
+}
 
+// ArduPilot flight modes. Not every firmware supports every mode.
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum VehicleMode {
-    LOITER,
+    ACRO,
+    ALT_HOLD,
+    AUTO,
+    AUTO_RTL,
+    AUTOROTATE,
+    AUTOTUNE,
+    AVOID_ADSB,
+    BRAKE,
+    CIRCLE,
+    CRUISE,
+    DOCK,
+    DRIFT,
+    FLIP,
+    FLOWHOLD,
+    FLY_BY_WIRE_A,
+    FLY_BY_WIRE_B,
+    FOLLOW,
     GUIDED,
+    GUIDED_NOGPS,
+    HOLD,
+    INITIALISING,
+    LAND,
+    LOITER,
+    LOITER_ALT_QLAND,
+    MANUAL,
+    POSHOLD,
+    QACRO,
+    QAUTOTUNE,
+    QHOVER,
+    QLAND,
+    QLOITER,
+    QRTL,
+    QSTABILIZE,
+    RTL,
+    SIMPLE,
+    SMART_RTL,
+    SPORT,
+    STABILIZE,
+    STEERING,
+    SYSTEMID,
+    TAKEOFF,
+    THERMAL,
+    THROW,
+    TRAINING,
+    ZIGZAG,
+}
+
+const COPTER_MODES: &'static [(VehicleMode, u32)] = &[(VehicleMode::STABILIZE, 0),
+                                                      (VehicleMode::ACRO, 1),
+                                                      (VehicleMode::ALT_HOLD, 2),
+                                                      (VehicleMode::AUTO, 3),
+                                                      (VehicleMode::GUIDED, 4),
+                                                      (VehicleMode::LOITER, 5),
+                                                      (VehicleMode::RTL, 6),
+                                                      (VehicleMode::CIRCLE, 7),
+                                                      (VehicleMode::LAND, 9),
+                                                      (VehicleMode::DRIFT, 11),
+                                                      (VehicleMode::SPORT, 13),
+                                                      (VehicleMode::FLIP, 14),
+                                                      (VehicleMode::AUTOTUNE, 15),
+                                                      (VehicleMode::POSHOLD, 16),
+                                                      (VehicleMode::BRAKE, 17),
+                                                      (VehicleMode::THROW, 18),
+                                                      (VehicleMode::AVOID_ADSB, 19),
+                                                      (VehicleMode::GUIDED_NOGPS, 20),
+                                                      (VehicleMode::SMART_RTL, 21),
+                                                      (VehicleMode::FLOWHOLD, 22),
+                                                      (VehicleMode::FOLLOW, 23),
+                                                      (VehicleMode::ZIGZAG, 24),
+                                                      (VehicleMode::SYSTEMID, 25),
+                                                      (VehicleMode::AUTOROTATE, 26),
+                                                      (VehicleMode::AUTO_RTL, 27)];
+
+const PLANE_MODES: &'static [(VehicleMode, u32)] = &[(VehicleMode::MANUAL, 0),
+                                                     (VehicleMode::CIRCLE, 1),
+                                                     (VehicleMode::STABILIZE, 2),
+                                                     (VehicleMode::TRAINING, 3),
+                                                     (VehicleMode::ACRO, 4),
+                                                     (VehicleMode::FLY_BY_WIRE_A, 5),
+                                                     (VehicleMode::FLY_BY_WIRE_B, 6),
+                                                     (VehicleMode::CRUISE, 7),
+                                                     (VehicleMode::AUTOTUNE, 8),
+                                                     (VehicleMode::AUTO, 10),
+                                                     (VehicleMode::RTL, 11),
+                                                     (VehicleMode::LOITER, 12),
+                                                     (VehicleMode::TAKEOFF, 13),
+                                                     (VehicleMode::AVOID_ADSB, 14),
+                                                     (VehicleMode::GUIDED, 15),
+                                                     (VehicleMode::INITIALISING, 16),
+                                                     (VehicleMode::QSTABILIZE, 17),
+                                                     (VehicleMode::QHOVER, 18),
+                                                     (VehicleMode::QLOITER, 19),
+                                                     (VehicleMode::QLAND, 20),
+                                                     (VehicleMode::QRTL, 21),
+                                                     (VehicleMode::QAUTOTUNE, 22),
+                                                     (VehicleMode::QACRO, 23),
+                                                     (VehicleMode::THERMAL, 24),
+                                                     (VehicleMode::LOITER_ALT_QLAND, 25)];
+
+const ROVER_MODES: &'static [(VehicleMode, u32)] = &[(VehicleMode::MANUAL, 0),
+                                                     (VehicleMode::ACRO, 1),
+                                                     (VehicleMode::STEERING, 3),
+                                                     (VehicleMode::HOLD, 4),
+                                                     (VehicleMode::LOITER, 5),
+                                                     (VehicleMode::FOLLOW, 6),
+                                                     (VehicleMode::SIMPLE, 7),
+                                                     (VehicleMode::DOCK, 8),
+                                                     (VehicleMode::CIRCLE, 9),
+                                                     (VehicleMode::AUTO, 10),
+                                                     (VehicleMode::RTL, 11),
+                                                     (VehicleMode::SMART_RTL, 12),
+                                                     (VehicleMode::GUIDED, 15),
+                                                     (VehicleMode::INITIALISING, 16)];
+
+impl VehicleMode {
+    // The HEARTBEAT custom_mode for this mode, or `None` if `firmware`
+    // doesn't support it.
+    pub fn custom_mode(&self, firmware: Firmware) -> Option<u32> {
+        firmware.modes().iter().find(|x| x.0 == *self).map(|x| x.1)
+    }
+
+    pub fn from_custom_mode(firmware: Firmware, custom_mode: u32) -> Option<VehicleMode> {
+        firmware.modes().iter().find(|x| x.1 == custom_mode).map(|x| x.0)
+    }
+
+    // Parses a mode name such as "GUIDED", ignoring case.
+    pub fn from_name(name: &str) -> Option<VehicleMode> {
+        let name = name.to_uppercase();
+        COPTER_MODES.iter()
+                    .chain(PLANE_MODES)
+                    .chain(ROVER_MODES)
+                    .map(|x| x.0)
+                    .find(|mode| format!("{:?}", mode) == name)
+    }
 }
 
 #[derive(Clone)]
 pub struct Parameters {
-    values: HashMap<String, f32>,
+    values: HashMap<String, ParamValue>,
     indexes: Vec<Option<String>>,
     missing: BitVec,
+    encoding: ParamEncoding,
+    metadata: Option<Rc<ParamMetadataDb>>,
+    // Values being set from here, so their echoes are reported as local.
+    local_sets: Arc<Mutex<HashMap<String, ParamValue>>>,
+    listeners: Rc<RefCell<ParamListeners>>,
+    rc_maps: HashMap<u8, String>,
     connection: Rc<RefCell<VehicleConnection>>,
 }
 
+// Tuning range for a parameter bound to an RC channel with `map_rc`. The
+// parameter is set to `value0 + scale * rc` for an RC input in [-1, 1],
+// clamped to [min, max].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RcMapping {
+    pub value0: f32,
+    pub scale: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+struct ParamListener {
+    id: usize,
+    name: Option<String>,
+    callback: Rc<RefCell<Box<FnMut(&ParamChangeEvent)>>>,
+}
+
+// Shared by every clone of `Parameters`, so ids stay unique.
+struct ParamListeners {
+    next_id: usize,
+    list: Vec<ParamListener>,
+}
+
 impl Parameters {
     pub fn new(connection: Rc<RefCell<VehicleConnection>>) -> Parameters {
         Parameters {
             values: HashMap::new(),
             indexes: vec![],
             missing: BitVec::new(),
+            encoding: ParamEncoding::CastFloat,
+            metadata: None,
+            local_sets: Arc::new(Mutex::new(HashMap::new())),
+            listeners: Rc::new(RefCell::new(ParamListeners {
+                next_id: 0,
+                list: vec![],
+            })),
+            rc_maps: HashMap::new(),
             connection: connection,
         }
     }
@@ -43,76 +284,528 @@ impl Parameters {
         }
     }
 
-    fn assign(&mut self, index: u16, name: &str, value: f32) {
-        self.values.insert(name.into(), value);
+    fn assign(&mut self, index: u16, name: &str, value: ParamValue) {
+        let old = self.values.insert(name.into(), value);
         if index != 65535 {
             self.indexes[index as usize] = Some(name.into());
             self.missing.set(index as usize, true);
         }
+
+        let local = {
+            let mut local_sets = self.local_sets.lock().unwrap();
+            let local = local_sets.get(name).map_or(false, |&x| same_value(x, value));
+            if local {
+                local_sets.remove(name);
+            }
+            local
+        };
+
+        match old {
+            Some(old) if old != value => {
+                let event = ParamChangeEvent {
+                    name: name.into(),
+                    old: old,
+                    new: value,
+                    local: local,
+                };
+                // Callbacks may subscribe or unsubscribe, so they are
+                // collected before any is called. One that is already running
+                // further up the stack is skipped.
+                let callbacks: Vec<_> = self.listeners
+                                            .borrow()
+                                            .list
+                                            .iter()
+                                            .filter(|x| {
+                                                x.name.as_ref().map_or(true, |x| *x == event.name)
+                                            })
+                                            .map(|x| x.callback.clone())
+                                            .collect();
+                for callback in callbacks {
+                    if let Ok(mut callback) = callback.try_borrow_mut() {
+                        (*callback)(&event);
+                    }
+                }
+            }
+            _ => {}
+        }
     }
 
-    pub fn get(&self, name: &str) -> Option<&f32> {
-        self.values.get(name)
+    // Calls `callback` whenever a cached value changes, for the parameter
+    // `name` or for every parameter if `None`. Values received for the first
+    // time are not reported. Returns an id for `unsubscribe`. Callbacks may
+    // subscribe and unsubscribe; changes take effect from the next event.
+    pub fn subscribe(&mut self,
+                     name: Option<&str>,
+                     callback: Box<FnMut(&ParamChangeEvent)>)
+                     -> usize {
+        let mut listeners = self.listeners.borrow_mut();
+        let id = listeners.next_id;
+        listeners.next_id += 1;
+        listeners.list.push(ParamListener {
+            id: id,
+            name: name.map(|x| x.into()),
+            callback: Rc::new(RefCell::new(callback)),
+        });
+        id
     }
 
-    pub fn set(&mut self, name: &str, value: f32) -> Future<(), ()> {
-        let (tx, future) = Future::<(), ()>::pair();
+    // Like `subscribe`, but delivers events over a channel.
+    pub fn subscribe_channel(&mut self, name: Option<&str>) -> Receiver<ParamChangeEvent> {
+        let (tx, rx) = channel();
+        self.subscribe(name,
+                       Box::new(move |event| {
+                           let _ = tx.send(event.clone());
+                       }));
+        rx
+    }
+
+    pub fn unsubscribe(&mut self, id: usize) {
+        self.listeners.borrow_mut().list.retain(|x| x.id != id);
+    }
+
+    pub fn get(&self, name: &str) -> Option<ParamValue> {
+        self.values.get(name).cloned()
+    }
+
+    // Sets a parameter, converting the value to the parameter's known type.
+    // Parameters not yet received are read first to learn their type.
+    pub fn set(&mut self, name: &str, value: f32) -> Future<(), ParamError> {
+        if let Err(reason) = self.validate(name, value) {
+            return Future::error(ParamError::Invalid(reason));
+        }
+        let typed = self.values.get(name).map(|current| current.with_value(value));
+        self.send_set(name, value, typed)
+    }
+
+    // Sets a parameter with an explicit type. Values outside the range given
+    // by loaded metadata fail without being sent.
+    pub fn set_value(&mut self, name: &str, value: ParamValue) -> Future<(), ParamError> {
+        if let Err(reason) = self.validate(name, value.as_f32()) {
+            return Future::error(ParamError::Invalid(reason));
+        }
+        self.send_set(name, value.as_f32(), Some(value))
+    }
+
+    // Sends `value` as `typed`, or reads the parameter first if its type is
+    // unknown, and waits for the vehicle to echo it. Requests are resent
+    // after a quiet period. Fails if the vehicle echoes a different value or
+    // stops replying, forgetting the set so that it isn't reported as local.
+    fn send_set(&mut self,
+                name: &str,
+                value: f32,
+                typed: Option<ParamValue>)
+                -> Future<(), ParamError> {
+        let (tx, future) = Future::<(), ParamError>::pair();
 
         let mut conn = self.connection.borrow_mut();
 
+        let outbox = conn.outbox();
+        let encoding = self.encoding;
+        let local_sets = self.local_sets.clone();
+        let local = typed.unwrap_or(ParamValue::F32(value));
+        local_sets.lock().unwrap().insert(name.into(), local);
+
         let name_closure: String = name.into();
-        conn.complete(tx,
-                      Box::new(move |msg| {
-                          if let DkMessage::PARAM_VALUE(data) = msg {
-                              parse_mavlink_string(&data.param_id) == name_closure &&
-                              data.param_value == value
-                          } else {
-                              false
-                          }
-                      }));
+        let mut typed = typed;
+        let mut tx = Some(tx);
+        let mut attempts = 1;
+        let mut last_sent = time::precise_time_ns();
+        conn.watch(Box::new(move |msg| {
+            let now = time::precise_time_ns();
+            let received = match msg {
+                DkMessage::PARAM_VALUE(ref data) if parse_mavlink_string(&data.param_id) ==
+                                                    name_closure => {
+                    Some(ParamValue::decode(data.param_value, data.param_type, encoding))
+                }
+                _ => None,
+            };
+
+            let result = match (received, typed) {
+                (Some(received), Some(typed)) if same_value(received, typed) => Ok(()),
+                (Some(received), Some(_)) => Err(ParamError::Rejected(received)),
+                (Some(received), None) => {
+                    let value = received.with_value(value);
+                    if same_value(received, value) {
+                        Ok(())
+                    } else {
+                        typed = Some(value);
+                        attempts = 1;
+                        last_sent = now;
+                        send_param_set(&outbox, &name_closure, value, encoding);
+                        return false;
+                    }
+                }
+                (None, _) if now - last_sent < PARAM_QUIET_NS => return false,
+                (None, _) if attempts < PARAM_SET_ATTEMPTS => {
+                    attempts += 1;
+                    last_sent = now;
+                    match typed {
+                        Some(typed) => send_param_set(&outbox, &name_closure, typed, encoding),
+                        None => send_param_read(&outbox, &name_closure),
+                    }
+                    return false;
+                }
+                (None, _) => Err(ParamError::NoReply),
+            };
+
+            if let Some(tx) = tx.take() {
+                match result {
+                    Ok(()) => tx.complete(()),
+                    Err(e) => {
+                        forget_local_set(&local_sets, &name_closure, local);
+                        tx.fail(e);
+                    }
+                }
+            }
+            true
+        }));
+
+        match typed {
+            Some(typed) => send_param_set(&outbox, name, typed, encoding),
+            None => send_param_read(&outbox, name),
+        }
+
+        future
+    }
+
+    pub fn load_metadata<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.metadata = Some(Rc::new(try!(ParamMetadataDb::load(path))));
+        Ok(())
+    }
+
+    pub fn metadata(&self, name: &str) -> Option<&ParamMetadata> {
+        self.metadata.as_ref().and_then(|db| db.get(name))
+    }
+
+    pub fn validate(&self, name: &str, value: f32) -> Result<(), String> {
+        match self.metadata {
+            Some(ref db) => db.validate(name, value),
+            None => Ok(()),
+        }
+    }
+
+    // Returns every cached parameter, sorted by name.
+    pub fn list(&self) -> Vec<(String, ParamValue)> {
+        let mut list: Vec<(String, ParamValue)> = self.values
+                                                      .iter()
+                                                      .map(|(k, v)| (k.clone(), *v))
+                                                      .collect();
+        list.sort_by(|a, b| a.0.cmp(&b.0));
+        list
+    }
+
+    // Compares desired values, such as those loaded from a parameter file,
+    // against the cache and returns the ones that differ. Values are
+    // converted to each parameter's cached type.
+    pub fn diff(&self, desired: &[(String, f64)]) -> Vec<ParamChange> {
+        desired.iter()
+               .filter_map(|&(ref name, value)| {
+                   let current = self.get(name);
+                   let desired = match current {
+                       Some(current) => current.with_value_f64(value),
+                       None => ParamValue::F32(value as f32),
+                   };
+                   if current == Some(desired) {
+                       None
+                   } else {
+                       Some(ParamChange {
+                           name: name.clone(),
+                           current: current,
+                           desired: desired,
+                       })
+                   }
+               })
+               .collect()
+    }
+
+    // Sets each changed parameter in turn, waiting for the vehicle to echo
+    // the new value before moving on. Parameters that are rejected or not
+    // acknowledged after several attempts are reported in the summary.
+    pub fn apply(&mut self, changes: Vec<ParamChange>) -> Future<ApplySummary, ()> {
+        let (tx, future) = Future::<ApplySummary, ()>::pair();
+
+        let mut summary = ApplySummary {
+            applied: vec![],
+            failed: vec![],
+        };
+        let mut pending = VecDeque::new();
+        for change in changes {
+            if self.validate(&change.name, change.desired.as_f32()).is_ok() {
+                self.local_sets.lock().unwrap().insert(change.name.clone(), change.desired);
+                pending.push_back(change);
+            } else {
+                summary.failed.push((change.name.clone(), change.current));
+            }
+        }
+        let mut current = match pending.pop_front() {
+            Some(change) => change,
+            None => {
+                tx.complete(summary);
+                return future;
+            }
+        };
+
+        let mut conn = self.connection.borrow_mut();
+        let outbox = conn.outbox();
+        let encoding = self.encoding;
+        let local_sets = self.local_sets.clone();
+        let (first_name, first_value) = (current.name.clone(), current.desired);
+
+        let mut tx = Some(tx);
+        let mut attempts = 1;
+        let mut last_sent = time::precise_time_ns();
+        conn.watch(Box::new(move |msg| {
+            let now = time::precise_time_ns();
+            match msg {
+                DkMessage::PARAM_VALUE(ref data) if parse_mavlink_string(&data.param_id) ==
+                                                    current.name => {
+                    let value = ParamValue::decode(data.param_value, data.param_type, encoding);
+                    if same_value(value, current.desired) {
+                        summary.applied.push(current.name.clone());
+                    } else {
+                        forget_local_set(&local_sets, &current.name, current.desired);
+                        summary.failed.push((current.name.clone(), Some(value)));
+                    }
+                }
+                _ => {
+                    if now - last_sent < PARAM_QUIET_NS {
+                        return false;
+                    }
+                    last_sent = now;
+                    if attempts < PARAM_SET_ATTEMPTS {
+                        attempts += 1;
+                        send_param_set(&outbox, &current.name, current.desired, encoding);
+                        return false;
+                    }
+                    forget_local_set(&local_sets, &current.name, current.desired);
+                    summary.failed.push((current.name.clone(), None));
+                }
+            }
+
+            match pending.pop_front() {
+                Some(change) => {
+                    current = change;
+                    attempts = 1;
+                    last_sent = now;
+                    send_param_set(&outbox, &current.name, current.desired, encoding);
+                    false
+                }
+                None => {
+                    if let Some(tx) = tx.take() {
+                        tx.complete(summary.clone());
+                    }
+                    true
+                }
+            }
+        }));
+
+        send_param_set(&conn.outbox(), &first_name, first_value, encoding);
+
+        future
+    }
+
+    // Requests a single parameter by name, resolving to its current value.
+    pub fn fetch(&mut self, name: &str) -> Future<ParamValue, ()> {
+        let name_closure: String = name.into();
+        self.request_read(name, -1, Box::new(move |data| {
+            parse_mavlink_string(&data.param_id) == name_closure
+        }))
+    }
+
+    // Requests a single parameter by index, resolving to its current value.
+    // PARAM_REQUEST_READ carries the index as an i16, so indexes above 32767
+    // fail without sending anything.
+    pub fn fetch_index(&mut self, index: u16) -> Future<ParamValue, ()> {
+        if index > i16::MAX as u16 {
+            return Future::error(());
+        }
+        self.request_read("", index as i16, Box::new(move |data| {
+            data.param_index == index
+        }))
+    }
 
-        conn.send(DkMessage::PARAM_SET(PARAM_SET_DATA {
-            param_value: value,
-            target_system: 0,
-            target_component: 0,
-            param_id: name.chars().chain(repeat(0 as char)).take(16).map(|x| x as u8).collect(),
-            param_type: 0,
+    // Sends PARAM_REQUEST_READ until a matching PARAM_VALUE arrives,
+    // resending after a quiet period and failing once attempts run out.
+    fn request_read(&mut self,
+                    name: &str,
+                    index: i16,
+                    mut matches: Box<FnMut(&PARAM_VALUE_DATA) -> bool + Send>)
+                    -> Future<ParamValue, ()> {
+        let (tx, future) = Future::<ParamValue, ()>::pair();
+
+        let mut conn = self.connection.borrow_mut();
+        let outbox = conn.outbox();
+
+        let encoding = self.encoding;
+        let name_closure: String = name.into();
+        let mut tx = Some(tx);
+        let mut attempts = 1;
+        let mut last_sent = time::precise_time_ns();
+        conn.watch(Box::new(move |msg| {
+            match msg {
+                DkMessage::PARAM_VALUE(ref data) if matches(data) => {
+                    if let Some(tx) = tx.take() {
+                        tx.complete(ParamValue::decode(data.param_value,
+                                                       data.param_type,
+                                                       encoding));
+                    }
+                    true
+                }
+                _ => {
+                    let now = time::precise_time_ns();
+                    if now - last_sent < PARAM_QUIET_NS {
+                        return false;
+                    }
+                    if attempts >= PARAM_SET_ATTEMPTS {
+                        if let Some(tx) = tx.take() {
+                            tx.fail(());
+                        }
+                        return true;
+                    }
+                    attempts += 1;
+                    last_sent = now;
+                    send_param_request_read(&outbox, &name_closure, index);
+                    false
+                }
+            }
         }));
 
+        send_param_request_read(&outbox, name, index);
+
         future
     }
 
+    // Binds a parameter to a tuning channel, such as a knob on the
+    // transmitter. `rc_index` is the autopilot's parameter RC channel index,
+    // not the RC channel number. The protocol has no acknowledgement.
+    pub fn map_rc(&mut self, name: &str, rc_index: u8, mapping: RcMapping) {
+        self.send_map_rc(name, -1, rc_index, mapping);
+        self.rc_maps.insert(rc_index, name.into());
+    }
+
+    pub fn clear_rc_map(&mut self, rc_index: u8) {
+        self.send_map_rc("",
+                         -2,
+                         rc_index,
+                         RcMapping {
+                             value0: 0.0,
+                             scale: 0.0,
+                             min: 0.0,
+                             max: 0.0,
+                         });
+        self.rc_maps.remove(&rc_index);
+    }
+
+    // Clears every binding made through `map_rc`.
+    pub fn clear_rc_maps(&mut self) {
+        let indexes: Vec<u8> = self.rc_maps.keys().cloned().collect();
+        for rc_index in indexes {
+            self.clear_rc_map(rc_index);
+        }
+    }
+
+    pub fn rc_maps(&self) -> &HashMap<u8, String> {
+        &self.rc_maps
+    }
+
+    fn send_map_rc(&mut self, name: &str, index: i16, rc_index: u8, mapping: RcMapping) {
+        let conn = self.connection.borrow();
+        conn.send(DkMessage::PARAM_MAP_RC(PARAM_MAP_RC_DATA {
+            param_value0: mapping.value0,
+            scale: mapping.scale,
+            param_value_min: mapping.min,
+            param_value_max: mapping.max,
+            param_index: index,
+            target_system: conn.target_system,
+            target_component: conn.target_component,
+            param_id: encode_param_id(name),
+            parameter_rc_channel_index: rc_index,
+        }));
+    }
+
     pub fn complete(&self) -> Future<(), ()> {
+        self.complete_with_progress(Box::new(|_, _| {}))
+    }
+
+    // Waits for the full parameter table, calling `progress` with the number
+    // of parameters received and the total as they arrive. Indexes still
+    // missing after a quiet period are re-requested individually, and the
+    // download restarts if the vehicle reports a different parameter count.
+    pub fn complete_with_progress(&self,
+                                  mut progress: Box<FnMut(usize, usize) + Send>)
+                                  -> Future<(), ()> {
         let (tx, future) = Future::<(), ()>::pair();
 
         // Create the bit vector
         if self.missing.len() > 0 && self.missing.all() {
+            progress(self.missing.len(), self.missing.len());
             tx.complete(());
         } else {
             let mut conn = self.connection.borrow_mut();
+            let outbox = conn.outbox();
             let mut missing = self.missing.clone();
+            let mut last_value = time::precise_time_ns();
             conn.complete(tx,
                           Box::new(move |msg| {
+                              let now = time::precise_time_ns();
                               if let DkMessage::PARAM_VALUE(data) = msg {
-                                  // Resize the array if a new parameter is sent.
+                                  last_value = now;
+
+                                  // Parameter indexes are renumbered when the
+                                  // count changes, so start over.
                                   if data.param_count as usize != missing.len() {
+                                      if missing.len() > 0 {
+                                          send_request_list(&outbox);
+                                      }
                                       missing = BitVec::from_elem(data.param_count as usize, false);
                                   }
 
+                                  // Values sent in reply to a set have no index.
+                                  if (data.param_index as usize) < missing.len() {
+                                      missing.set(data.param_index as usize, true);
+                                  }
+
+                                  let received = missing.iter().filter(|x| *x).count();
+                                  progress(received, missing.len());
+
                                   // If we have a complete set, indicate as such.
-                                  missing.set(data.param_index as usize, true);
-                                  missing.all()
-                              } else {
-                                  false
+                                  return missing.all();
                               }
+
+                              if now - last_value > PARAM_QUIET_NS {
+                                  last_value = now;
+                                  if missing.len() == 0 {
+                                      send_request_list(&outbox);
+                                  } else {
+                                      let gaps = missing.iter()
+                                                        .enumerate()
+                                                        .filter(|&(_, x)| !x)
+                                                        .map(|(i, _)| i)
+                                                        .take(PARAM_RETRY_BATCH);
+                                      for index in gaps {
+                                          outbox.send(DkMessage::PARAM_REQUEST_READ(PARAM_REQUEST_READ_DATA {
+                                              param_index: index as i16,
+                                              target_system: outbox.target_system,
+                                              target_component: outbox.target_component,
+                                              param_id: encode_param_id(""),
+                                          }));
+                                      }
+                                  }
+                              }
+                              false
                           }));
         }
 
         future
     }
 
+    // Returns the number of parameters received and the total, if known.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.available(), self.indexes.len())
+    }
+
     pub fn remaining(&self) -> usize {
-        self.indexes.iter().filter(|x| x.is_some()).count()
+        self.indexes.iter().filter(|x| x.is_none()).count()
     }
 
     pub fn available(&self) -> usize {
@@ -120,26 +813,170 @@ impl Parameters {
     }
 }
 
-trait Distance<T> {
+fn send_request_list(outbox: &Outbox) {
+    outbox.send(DkMessage::PARAM_REQUEST_LIST(PARAM_REQUEST_LIST_DATA {
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+    }));
+}
+
+// Whether two parameter values are numerically equal, whatever their types.
+// The vehicle echoes a set value in the parameter's own type, which may not
+// be the type it was sent as. Every value converts to f64 exactly, so
+// integers compare exactly too.
+fn same_value(a: ParamValue, b: ParamValue) -> bool {
+    a.as_f64() == b.as_f64()
+}
+
+// Removes a local set that failed, unless it has since been replaced.
+fn forget_local_set(local_sets: &Mutex<HashMap<String, ParamValue>>,
+                    name: &str,
+                    value: ParamValue) {
+    let mut local_sets = local_sets.lock().unwrap();
+    if local_sets.get(name).map_or(false, |&x| same_value(x, value)) {
+        local_sets.remove(name);
+    }
+}
+
+pub fn send_param_read(outbox: &Outbox, name: &str) {
+    send_param_request_read(outbox, name, -1);
+}
+
+// Requests a parameter by index, or by name when `index` is -1.
+fn send_param_request_read(outbox: &Outbox, name: &str, index: i16) {
+    outbox.send(DkMessage::PARAM_REQUEST_READ(PARAM_REQUEST_READ_DATA {
+        param_index: index,
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+        param_id: encode_param_id(name),
+    }));
+}
+
+pub fn send_param_set(outbox: &Outbox, name: &str, value: ParamValue, encoding: ParamEncoding) {
+    let (param_value, param_type) = value.encode(encoding);
+    outbox.send(DkMessage::PARAM_SET(PARAM_SET_DATA {
+        param_value: param_value,
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+        param_id: encode_param_id(name),
+        param_type: param_type,
+    }));
+}
+
+// Sends COMMAND_LONG with the first two parameters set.
+fn send_command(outbox: &Outbox, command: u16, param1: f32, param2: f32) {
+    outbox.send(DkMessage::COMMAND_LONG(COMMAND_LONG_DATA {
+        target_system: outbox.target_system,
+        target_component: outbox.target_component,
+        command: command,
+        confirmation: 0,
+        param1: param1,
+        param2: param2,
+        param3: 0.0,
+        param4: 0.0,
+        param5: 0.0,
+        param6: 0.0,
+        param7: 0.0,
+    }));
+}
+
+pub fn encode_param_id(name: &str) -> Vec<u8> {
+    name.chars().chain(repeat(0 as char)).take(16).map(|x| x as u8).collect()
+}
+
+pub trait Distance<T> {
     fn distance_to(&self, &T) -> f32;
 }
 
+// Mean radius of the earth in meters.
+const EARTH_RADIUS: f64 = 6371008.8;
 
-#[derive(Clone, Debug)]
+// Great-circle distance in meters between two points in degrees * 1e7.
+fn haversine(lat1: i32, lon1: i32, lat2: i32, lon2: i32) -> f64 {
+    let (lat1, lon1) = ((lat1 as f64 / 1e7).to_radians(), (lon1 as f64 / 1e7).to_radians());
+    let (lat2, lon2) = ((lat2 as f64 / 1e7).to_radians(), (lon2 as f64 / 1e7).to_radians());
+    let a = ((lat2 - lat1) / 2.0).sin().powi(2) +
+            lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * a.sqrt().asin()
+}
+
+// WGS84 semi-major axis in meters and flattening.
+const WGS84_A: f64 = 6378137.0;
+const WGS84_F: f64 = 1.0 / 298.257223563;
+
+// Distance in meters on the WGS84 ellipsoid between two points in degrees *
+// 1e7, by Vincenty's inverse formula. Accurate to well under a millimeter,
+// against up to 0.5% for `haversine`. Falls back to `haversine` for nearly
+// antipodal points, where the iteration doesn't converge.
+fn vincenty(lat1: i32, lon1: i32, lat2: i32, lon2: i32) -> f64 {
+    let b = WGS84_A * (1.0 - WGS84_F);
+    // Longitudes are converted before subtracting, since the difference
+    // overflows i32 across the antimeridian, and wrapped to [-180, 180).
+    let l = ((lon2 as f64 / 1e7 - lon1 as f64 / 1e7 + 540.0) % 360.0 - 180.0).to_radians();
+    let u1 = ((1.0 - WGS84_F) * (lat1 as f64 / 1e7).to_radians().tan()).atan();
+    let u2 = ((1.0 - WGS84_F) * (lat2 as f64 / 1e7).to_radians().tan()).atan();
+    let (sin_u1, cos_u1) = u1.sin_cos();
+    let (sin_u2, cos_u2) = u2.sin_cos();
+
+    let mut lambda = l;
+    for _ in 0..200 {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let sin_sigma = ((cos_u2 * sin_lambda).powi(2) +
+                         (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+                            .sqrt();
+        if sin_sigma == 0.0 {
+            return 0.0;
+        }
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        let cos2_alpha = 1.0 - sin_alpha * sin_alpha;
+        // Zero along the equator.
+        let cos_2sigma_m = if cos2_alpha == 0.0 {
+            0.0
+        } else {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha
+        };
+        let c = WGS84_F / 16.0 * cos2_alpha * (4.0 + WGS84_F * (4.0 - 3.0 * cos2_alpha));
+        let previous = lambda;
+        lambda = l +
+                 (1.0 - c) * WGS84_F * sin_alpha *
+                 (sigma +
+                  c * sin_sigma *
+                  (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+        if (lambda - previous).abs() < 1e-12 {
+            let u_sq = cos2_alpha * (WGS84_A.powi(2) - b.powi(2)) / b.powi(2);
+            let big_a = 1.0 +
+                        u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+            let big_b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+            let delta_sigma = big_b * sin_sigma *
+                              (cos_2sigma_m +
+                               big_b / 4.0 *
+                               (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2)) -
+                                big_b / 6.0 * cos_2sigma_m * (-3.0 + 4.0 * sin_sigma.powi(2)) *
+                                (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+            return b * big_a * (sigma - delta_sigma);
+        }
+    }
+    haversine(lat1, lon1, lat2, lon2)
+}
+
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct LocationGlobalRelative {
     pub alt: i32,
     pub lat: i32,
     pub lon: i32,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct LocationGlobal {
     pub alt: i32,
     pub lat: i32,
     pub lon: i32,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct LocationLocal {
     pub x: f32,
     pub y: f32,
@@ -162,14 +999,69 @@ impl Distance<LocationLocal> for LocationLocal {
     }
 }
 
+impl Distance<LocationGlobal> for LocationGlobal {
+    fn distance_to(&self, to: &LocationGlobal) -> f32 {
+        haversine(self.lat, self.lon, to.lat, to.lon) as f32
+    }
+}
+
 // #[derive(Clone, Debug)]
 pub struct Vehicle {
     pub parameters: Parameters,
+    pub mission: Mission,
     pub location_global: Option<LocationGlobal>,
     pub location_global_relative: Option<LocationGlobalRelative>,
     pub location_local: Option<LocationLocal>,
+    pub attitude: Option<Attitude>,
+    pub velocity: Option<Velocity>,
+    // Degrees from north.
+    pub heading: Option<i16>,
+    // Meters per second.
+    pub groundspeed: Option<f32>,
+    pub airspeed: Option<f32>,
+    pub gps: Option<GpsInfo>,
+    pub battery: Option<Battery>,
+    pub system_status: Option<SystemStatus>,
+    pub armed: bool,
+    // Autopilot-specific custom mode from HEARTBEAT; see
+    // `VehicleMode::from_custom_mode`.
+    pub mode: Option<u32>,
+    // Known once the autopilot's heartbeat reports an ArduPilot vehicle type.
+    pub firmware: Option<Firmware>,
+    pub ekf_ok: bool,
+    pub rangefinder: Option<Rangefinder>,
+    pub home_location: Option<LocationGlobal>,
+    // Set when the vehicle stops heartbeating, until it resumes.
+    pub link_lost: bool,
+    // MAV_TYPE of each component of the vehicle's system that has sent a
+    // heartbeat, by component id.
+    pub components: BTreeMap<u8, u8>,
+    updated: HashMap<&'static str, u64>,
+    listeners: Vec<AttributeListener>,
+    next_listener: usize,
     connection: Rc<RefCell<VehicleConnection>>,
     master_heartbeat: bool,
+    // System and component id of the autopilot, from its first heartbeat.
+    autopilot: Option<(u8, u8)>,
+    intervals: Arc<Mutex<IntervalQueue>>,
+}
+
+// Message interval requests not yet acknowledged. The acknowledgement of
+// MAV_CMD_SET_MESSAGE_INTERVAL doesn't say which message it was for, so
+// requests are sent one at a time, each after the previous one's ack.
+struct IntervalQueue {
+    // Message id, interval and completion of each request; the first has
+    // been sent.
+    pending: VecDeque<(u8, i32, Complete<(), ()>)>,
+}
+
+struct AttributeListener {
+    id: usize,
+    attr: String,
+    options: ListenerOptions,
+    callback: Box<FnMut(&str, &AttributeValue)>,
+    last_value: Option<AttributeValue>,
+    last_fired: u64,
 }
 
 impl Vehicle {
@@ -177,21 +1069,43 @@ impl Vehicle {
         let connection = Rc::new(RefCell::new(conn));
         Vehicle {
             parameters: Parameters::new(connection.clone()),
+            mission: Mission::new(connection.clone()),
             location_global: None,
             location_global_relative: None,
             location_local: None,
+            attitude: None,
+            velocity: None,
+            heading: None,
+            groundspeed: None,
+            airspeed: None,
+            gps: None,
+            battery: None,
+            system_status: None,
+            armed: false,
+            mode: None,
+            firmware: None,
+            ekf_ok: false,
+            rangefinder: None,
+            home_location: None,
+            link_lost: false,
+            components: BTreeMap::new(),
+            updated: HashMap::new(),
+            listeners: vec![],
+            next_listener: 0,
             connection: connection,
             master_heartbeat: false,
+            autopilot: None,
+            intervals: Arc::new(Mutex::new(IntervalQueue { pending: VecDeque::new() })),
         }
     }
 
     pub fn update(&mut self, wait: bool) {
         if wait {
             let val = {
-                self.connection.borrow_mut().recv()
+                self.connection.borrow_mut().recv_with_header()
             };
-            if let Ok(msg) = val {
-                self.on_message(msg);
+            if let Ok((header, msg)) = val {
+                self.on_message(header, msg);
             } else {
                 return;
             }
@@ -200,30 +1114,195 @@ impl Vehicle {
         // Get remaining queued packets
         loop {
             let val = {
-                self.connection.borrow_mut().try_recv()
+                self.connection.borrow_mut().try_recv_with_header()
             };
-            if let Ok(msg) = val {
-                self.on_message(msg);
+            if let Ok((header, msg)) = val {
+                self.on_message(header, msg);
             } else {
                 break;
             }
         }
+
+        self.on_link_events();
+    }
+
+    // Like `update(true)`, but gives up waiting for a first message after
+    // `timeout`. Returns whether any message was handled.
+    pub fn update_timeout(&mut self, timeout: Duration) -> bool {
+        let val = {
+            self.connection.borrow_mut().recv_timeout_with_header(timeout)
+        };
+        match val {
+            Ok((header, msg)) => self.on_message(header, msg),
+            Err(..) => {
+                self.on_link_events();
+                return false;
+            }
+        }
+        self.update(false);
+        true
+    }
+
+    // Subscribes to messages by id without affecting `Vehicle` or other
+    // subscribers. See `VehicleConnection::subscribe`.
+    pub fn subscribe(&mut self, ids: &[u8], capacity: usize) -> Subscription {
+        self.connection.borrow_mut().subscribe(ids, capacity)
+    }
+
+    // See `VehicleConnection::stats`.
+    pub fn link_stats(&self) -> LinkStats {
+        self.connection.borrow().stats()
     }
 
     pub fn init(&mut self) {
-        self.send_heartbeat();
+        self.start_heartbeat();
         while !self.master_heartbeat {
             self.update(true);
         }
 
         self.request_parameters();
-        self.request_stream();
+        self.request_data_stream(StreamGroup::All, 10);
+        self.request_home();
+    }
+
+    // Returns the monotonic time in nanoseconds (see `time::precise_time_ns`)
+    // at which an attribute was last updated, or `None` if it never has been.
+    // Attributes are named after the fields of `Vehicle`, plus `heartbeat`.
+    pub fn last_update(&self, attr: &str) -> Option<u64> {
+        self.updated.get(attr).cloned()
+    }
+
+    // Seconds since the vehicle's last heartbeat.
+    pub fn last_heartbeat(&self) -> Option<f64> {
+        self.last_update("heartbeat")
+            .map(|t| (time::precise_time_ns() - t) as f64 / 1e9)
+    }
+
+    fn touch(&mut self, attrs: &[&'static str]) {
+        let now = time::precise_time_ns();
+        for attr in attrs {
+            self.updated.insert(*attr, now);
+            self.notify(attr, now);
+        }
+    }
+
+    // Returns the current value of a named attribute.
+    pub fn attribute(&self, attr: &str) -> Option<AttributeValue> {
+        match attr {
+            "location_global" => self.location_global.clone().map(AttributeValue::LocationGlobal),
+            "location_global_relative" => {
+                self.location_global_relative.clone().map(AttributeValue::LocationGlobalRelative)
+            }
+            "location_local" => self.location_local.map(AttributeValue::LocationLocal),
+            "attitude" => self.attitude.map(AttributeValue::Attitude),
+            "velocity" => self.velocity.map(AttributeValue::Velocity),
+            "heading" => self.heading.map(AttributeValue::Heading),
+            "groundspeed" => self.groundspeed.map(AttributeValue::Speed),
+            "airspeed" => self.airspeed.map(AttributeValue::Speed),
+            "gps" => self.gps.map(AttributeValue::Gps),
+            "battery" => self.battery.map(AttributeValue::Battery),
+            "system_status" => self.system_status.map(AttributeValue::SystemStatus),
+            "armed" => Some(AttributeValue::Flag(self.armed)),
+            "mode" => self.mode.map(AttributeValue::Mode),
+            "ekf_ok" => Some(AttributeValue::Flag(self.ekf_ok)),
+            "rangefinder" => self.rangefinder.map(AttributeValue::Rangefinder),
+            "home_location" => self.home_location.clone().map(AttributeValue::LocationGlobal),
+            "heartbeat" => self.last_heartbeat().map(AttributeValue::Heartbeat),
+            "link_lost" => Some(AttributeValue::Flag(self.link_lost)),
+            _ => None,
+        }
+    }
+
+    // Calls `callback` with the attribute name and value whenever `attr` is
+    // updated from incoming messages, or any attribute if `attr` is "*".
+    // Returns an id for `remove_attribute_listener`.
+    pub fn add_attribute_listener(&mut self,
+                                  attr: &str,
+                                  options: ListenerOptions,
+                                  callback: Box<FnMut(&str, &AttributeValue)>)
+                                  -> usize {
+        let id = self.next_listener;
+        self.next_listener += 1;
+        self.listeners.push(AttributeListener {
+            id: id,
+            attr: attr.into(),
+            options: options,
+            callback: callback,
+            last_value: None,
+            last_fired: 0,
+        });
+        id
     }
 
-    fn send_heartbeat(&mut self) {
-        self.connection.borrow_mut().send(DkMessage::HEARTBEAT(HEARTBEAT_DATA {
+    // Like `add_attribute_listener`, but delivers updates over a channel.
+    pub fn attribute_channel(&mut self,
+                             attr: &str,
+                             options: ListenerOptions)
+                             -> (usize, Receiver<(String, AttributeValue)>) {
+        let (tx, rx) = channel();
+        let id = self.add_attribute_listener(attr,
+                                             options,
+                                             Box::new(move |name, value| {
+                                                 let _ = tx.send((name.into(), value.clone()));
+                                             }));
+        (id, rx)
+    }
+
+    pub fn remove_attribute_listener(&mut self, id: usize) {
+        self.listeners.retain(|x| x.id != id);
+    }
+
+    fn notify(&mut self, attr: &str, now: u64) {
+        if self.listeners.is_empty() {
+            return;
+        }
+        let value = match self.attribute(attr) {
+            Some(value) => value,
+            None => return,
+        };
+
+        for listener in self.listeners.iter_mut() {
+            if listener.attr != attr && listener.attr != "*" {
+                continue;
+            }
+            if listener.options.changes_only && listener.last_value.as_ref() == Some(&value) {
+                continue;
+            }
+            if let Some(rate) = listener.options.max_rate {
+                if ((now - listener.last_fired) as f64) < 1e9 / rate as f64 {
+                    continue;
+                }
+            }
+            listener.last_value = Some(value.clone());
+            listener.last_fired = now;
+            (listener.callback)(attr, &value);
+        }
+    }
+
+    // Sets how long the vehicle may go without heartbeating before
+    // `link_lost` is set.
+    pub fn set_link_timeout(&mut self, timeout: Duration) {
+        self.connection.borrow().set_link_timeout(timeout);
+    }
+
+    fn on_link_events(&mut self) {
+        let events = self.connection.borrow_mut().take_events();
+        for event in events {
+            self.link_lost = match event {
+                LinkEvent::Lost(..) => true,
+                LinkEvent::Restored(..) => false,
+            };
+            self.touch(&["link_lost"]);
+        }
+    }
+
+    // Our heartbeat is sent by the event loop once a second so that the
+    // vehicle's GCS failsafe doesn't trigger while we're connected.
+    fn start_heartbeat(&mut self) {
+        let conn = self.connection.borrow();
+        conn.start_heartbeat(DkMessage::HEARTBEAT(HEARTBEAT_DATA {
             custom_mode: 0,
-            mavtype: 6,
+            mavtype: conn.source_type,
             autopilot: 8,
             base_mode: 0,
             system_status: 0,
@@ -231,33 +1310,218 @@ impl Vehicle {
         }));
     }
 
+    fn request_home(&mut self) {
+        let conn = self.connection.borrow();
+        conn.send(DkMessage::COMMAND_LONG(COMMAND_LONG_DATA {
+            target_system: conn.target_system,
+            target_component: conn.target_component,
+            command: 410,
+            confirmation: 0,
+            param1: 0.0,
+            param2: 0.0,
+            param3: 0.0,
+            param4: 0.0,
+            param5: 0.0,
+            param6: 0.0,
+            param7: 0.0,
+        }));
+    }
+
     fn request_parameters(&mut self) {
-        self.connection.borrow_mut().send(DkMessage::PARAM_REQUEST_LIST(PARAM_REQUEST_LIST_DATA {
-            target_system: 0,
-            target_component: 0,
+        send_request_list(&self.connection.borrow().outbox());
+    }
+
+    // Sets the rate of a stream group with REQUEST_DATA_STREAM. A rate of 0
+    // stops the group. Autopilots don't acknowledge this.
+    pub fn request_data_stream(&mut self, group: StreamGroup, hz: u16) {
+        let conn = self.connection.borrow();
+        conn.send(DkMessage::REQUEST_DATA_STREAM(REQUEST_DATA_STREAM_DATA {
+            target_system: conn.target_system,
+            target_component: conn.target_component,
+            req_stream_id: group.stream_id(),
+            req_message_rate: hz,
+            start_stop: if hz > 0 { 1 } else { 0 },
+        }));
+    }
+
+    // Sets the interval between messages with id `message_id` using
+    // MAV_CMD_SET_MESSAGE_INTERVAL. See `streams::interval_us` and the
+    // special values `INTERVAL_DISABLED` and `INTERVAL_DEFAULT`. Requests
+    // are queued and sent one at a time, so that each ack is matched to its
+    // request. Fails if the request is rejected or not acknowledged in time.
+    pub fn set_message_interval(&mut self, message_id: u8, interval_us: i32) -> Future<(), ()> {
+        let (tx, future) = Future::<(), ()>::pair();
+
+        let idle = {
+            let mut queue = self.intervals.lock().unwrap();
+            queue.pending.push_back((message_id, interval_us, tx));
+            queue.pending.len() == 1
+        };
+        if !idle {
+            return future;
+        }
+
+        let mut conn = self.connection.borrow_mut();
+
+        let outbox = conn.outbox();
+        let queue = self.intervals.clone();
+        let mut last_sent = time::precise_time_ns();
+        conn.watch(Box::new(move |msg| {
+            let now = time::precise_time_ns();
+            let accepted = match msg {
+                DkMessage::COMMAND_ACK(ref data) if data.command == 511 => {
+                    data.result == MAV_RESULT_ACCEPTED
+                }
+                _ if now - last_sent >= INTERVAL_ACK_TIMEOUT_NS => false,
+                _ => return false,
+            };
+
+            // Requests are resolved outside the lock, since resolving one may
+            // queue another.
+            let (done, next) = {
+                let mut queue = queue.lock().unwrap();
+                let done = queue.pending.pop_front();
+                let next = queue.pending.front().map(|&(message_id, interval_us, _)| {
+                    (message_id, interval_us)
+                });
+                (done, next)
+            };
+            if let Some((message_id, interval_us)) = next {
+                last_sent = now;
+                send_command(&outbox, 511, message_id as f32, interval_us as f32);
+            }
+            if let Some((_, _, tx)) = done {
+                if accepted {
+                    tx.complete(());
+                } else {
+                    tx.fail(());
+                }
+            }
+            next.is_none()
+        }));
+
+        send_command(&conn.outbox(), 511, message_id as f32, interval_us as f32);
+
+        future
+    }
+
+    // Requests the interval in microseconds between messages with id
+    // `message_id`, as reported in MESSAGE_INTERVAL. The request is resent
+    // if unanswered, and fails if rejected or never answered.
+    pub fn get_message_interval(&mut self, message_id: u8) -> Future<i32, ()> {
+        let (tx, future) = Future::<i32, ()>::pair();
+
+        let mut conn = self.connection.borrow_mut();
+
+        let outbox = conn.outbox();
+        let mut tx = Some(tx);
+        let mut attempts = 1;
+        let mut last_sent = time::precise_time_ns();
+        conn.watch(Box::new(move |msg| {
+            match msg {
+                DkMessage::MESSAGE_INTERVAL(ref data) if data.message_id == message_id as u16 => {
+                    if let Some(tx) = tx.take() {
+                        tx.complete(data.interval_us);
+                    }
+                    true
+                }
+                DkMessage::COMMAND_ACK(ref data) if data.command == 510 &&
+                                                    data.result != MAV_RESULT_ACCEPTED => {
+                    if let Some(tx) = tx.take() {
+                        tx.fail(());
+                    }
+                    true
+                }
+                _ => {
+                    let now = time::precise_time_ns();
+                    if now - last_sent < INTERVAL_ACK_TIMEOUT_NS {
+                        return false;
+                    }
+                    if attempts >= INTERVAL_GET_ATTEMPTS {
+                        if let Some(tx) = tx.take() {
+                            tx.fail(());
+                        }
+                        return true;
+                    }
+                    attempts += 1;
+                    last_sent = now;
+                    send_command(&outbox, 510, message_id as f32, 0.0);
+                    false
+                }
+            }
         }));
+
+        send_command(&conn.outbox(), 510, message_id as f32, 0.0);
+
+        future
     }
 
-    fn request_stream(&mut self) {
-        self.connection
-            .borrow_mut()
-            .send(DkMessage::REQUEST_DATA_STREAM(REQUEST_DATA_STREAM_DATA {
-                target_system: 0,
-                target_component: 0,
-                req_stream_id: 0,
-                req_message_rate: 10,
-                start_stop: 1,
-            }));
+    // Requests each stream group of `profile`, then sets each message rate
+    // with `set_message_interval`. Completes when every message interval has
+    // been acknowledged, and fails if any is rejected.
+    pub fn apply_stream_profile(&mut self, profile: &StreamProfile) -> Future<(), ()> {
+        for &(group, hz) in &profile.groups {
+            self.request_data_stream(group, hz);
+        }
+
+        let (tx, future) = Future::<(), ()>::pair();
+        if profile.messages.is_empty() {
+            tx.complete(());
+            return future;
+        }
+
+        let state = Arc::new(Mutex::new((profile.messages.len(), Some(tx))));
+        for &(message_id, hz) in &profile.messages {
+            let state = state.clone();
+            self.set_message_interval(message_id, interval_us(hz)).receive(move |result| {
+                let mut state = state.lock().unwrap();
+                state.0 -= 1;
+                let tx = match result {
+                    Ok(()) if state.0 > 0 => return,
+                    _ => state.1.take(),
+                };
+                if let Some(tx) = tx {
+                    match result {
+                        Ok(()) => tx.complete(()),
+                        Err(..) => tx.fail(()),
+                    }
+                }
+            });
+        }
+
+        future
     }
 
-    fn on_message(&mut self, pkt: DkMessage) {
+    fn on_message(&mut self, header: MavHeader, pkt: DkMessage) {
         match pkt {
-            DkMessage::HEARTBEAT(..) => {
-                self.send_heartbeat();
+            DkMessage::HEARTBEAT(data) => {
+                // Ignore heartbeats from other ground stations, and from
+                // components other than the autopilot such as gimbals and
+                // companion computers.
+                if data.autopilot != MAV_AUTOPILOT_INVALID && self.autopilot.is_none() {
+                    self.autopilot = Some((header.system_id, header.component_id));
+                }
+                if self.autopilot == Some((header.system_id, header.component_id)) {
+                    self.firmware = Firmware::from_mav_type(data.mavtype);
+                    self.parameters.encoding = ParamEncoding::for_autopilot(data.autopilot);
+                    self.armed = (data.base_mode & 128) != 0;
+                    self.mode = Some(data.custom_mode);
+                    self.system_status = Some(SystemStatus::from_mav_state(data.system_status));
+                    self.touch(&["heartbeat", "armed", "mode", "system_status"]);
+                }
                 // self.connection.borrow_mut().send(DkMessage::MISSION_REQUEST_LIST(MISSION_REQUEST_LIST_DATA {
                 //     target_system: 0,
                 //     target_component: 0,
                 // }));
+                // A connection that isn't routed for one system sees every
+                // system, so components are taken from the autopilot's.
+                let system_id = self.connection
+                                    .borrow()
+                                    .system_id
+                                    .or(self.autopilot.map(|(system_id, _)| system_id));
+                if data.mavtype != MAV_TYPE_GCS && system_id == Some(header.system_id) {
+                    self.components.insert(header.component_id, data.mavtype);
+                }
                 self.master_heartbeat = true;
             }
             DkMessage::STATUSTEXT(data) => {
@@ -266,12 +1530,73 @@ impl Vehicle {
             }
             DkMessage::PARAM_VALUE(data) => {
                 self.parameters.resize(data.param_count);
+                let value = ParamValue::decode(data.param_value,
+                                               data.param_type,
+                                               self.parameters.encoding);
                 self.parameters.assign(data.param_index,
                                        &parse_mavlink_string(&data.param_id),
-                                       data.param_value);
+                                       value);
+            }
+            DkMessage::ATTITUDE(data) => {
+                self.attitude = Some(Attitude {
+                    roll: data.roll,
+                    pitch: data.pitch,
+                    yaw: data.yaw,
+                    rollspeed: data.rollspeed,
+                    pitchspeed: data.pitchspeed,
+                    yawspeed: data.yawspeed,
+                });
+                self.touch(&["attitude"]);
+            }
+            DkMessage::VFR_HUD(data) => {
+                self.heading = Some(data.heading);
+                self.groundspeed = Some(data.groundspeed);
+                self.airspeed = Some(data.airspeed);
+                self.touch(&["heading", "groundspeed", "airspeed"]);
             }
-            DkMessage::ATTITUDE(..) => {
-                // println!("roll: {:?}\tpitch: {:?}\tyaw: {:?}", data.roll, data.pitch, data.yaw);
+            DkMessage::GPS_RAW_INT(data) => {
+                let dop = |x: u16| if x == 65535 { None } else { Some(x as f32 / 100.0) };
+                self.gps = Some(GpsInfo {
+                    fix_type: data.fix_type,
+                    satellites_visible: data.satellites_visible,
+                    eph: dop(data.eph),
+                    epv: dop(data.epv),
+                });
+                self.touch(&["gps"]);
+            }
+            DkMessage::SYS_STATUS(data) => {
+                self.battery = Some(Battery {
+                    voltage: data.voltage_battery as f32 / 1000.0,
+                    current: if data.current_battery == -1 {
+                        None
+                    } else {
+                        Some(data.current_battery as f32 / 100.0)
+                    },
+                    level: if data.battery_remaining == -1 {
+                        None
+                    } else {
+                        Some(data.battery_remaining as u8)
+                    },
+                });
+                // ArduPilot reports EKF health through the AHRS sensor bit.
+                self.ekf_ok = (data.onboard_control_sensors_health & MAV_SYS_STATUS_AHRS) != 0;
+                self.touch(&["battery", "ekf_ok"]);
+            }
+            DkMessage::DISTANCE_SENSOR(data) => {
+                self.rangefinder = Some(Rangefinder {
+                    distance: data.current_distance as f32 / 100.0,
+                    min_distance: data.min_distance as f32 / 100.0,
+                    max_distance: data.max_distance as f32 / 100.0,
+                });
+                self.touch(&["rangefinder"]);
+            }
+            DkMessage::HOME_POSITION(data) => {
+                self.home_location = Some(LocationGlobal {
+                    lat: data.latitude,
+                    lon: data.longitude,
+                    alt: data.altitude,
+                });
+                self.touch(&["home_location"]);
             }
             DkMessage::GLOBAL_POSITION_INT(data) => {
                 self.location_global = Some(LocationGlobal {
@@ -284,6 +1609,12 @@ impl Vehicle {
                     lon: data.lon,
                     alt: data.relative_alt,
                 });
+                self.velocity = Some(Velocity {
+                    vx: data.vx as f32 / 100.0,
+                    vy: data.vy as f32 / 100.0,
+                    vz: data.vz as f32 / 100.0,
+                });
+                self.touch(&["location_global", "location_global_relative", "velocity"]);
             }
             DkMessage::LOCAL_POSITION_NED(data) => {
                 self.location_local = Some(LocationLocal {
@@ -291,6 +1622,7 @@ impl Vehicle {
                     y: data.y,
                     z: data.z,
                 });
+                self.touch(&["location_local"]);
             }
             _ => {
                 // println!("dunno: {:?}", pkt);
@@ -298,7 +1630,15 @@ impl Vehicle {
         }
     }
 
+    // Switches flight mode, completing once the autopilot's heartbeat
+    // reports it. Fails if the firmware isn't known yet or doesn't support
+    // `mode`.
     pub fn set_mode(&mut self, mode: VehicleMode) -> Future<(), ()> {
+        let custom_mode = match self.firmware.and_then(|firmware| mode.custom_mode(firmware)) {
+            Some(custom_mode) => custom_mode,
+            None => return Future::error(()),
+        };
+
         let (tx, future) = Future::<(), ()>::pair();
 
         let mut conn = self.connection.borrow_mut();
@@ -306,45 +1646,78 @@ impl Vehicle {
         conn.complete(tx,
                       Box::new(move |msg| {
                           if let DkMessage::HEARTBEAT(data) = msg {
-                              (data.base_mode & 1 != 0) && (data.custom_mode == 4)
+                              data.autopilot != MAV_AUTOPILOT_INVALID &&
+                              (data.base_mode & 1 != 0) &&
+                              (data.custom_mode == custom_mode)
                           } else {
                               false
                           }
                       }));
 
         conn.send(DkMessage::SET_MODE(SET_MODE_DATA {
-            target_system: 0,
+            target_system: conn.target_system,
             base_mode: 1,
-            custom_mode: 4,
+            custom_mode: custom_mode,
         }));
 
         future
     }
 
     pub fn arm(&mut self) -> Future<(), ()> {
+        self.set_armed(true)
+    }
+
+    pub fn disarm(&mut self) -> Future<(), ()> {
+        self.set_armed(false)
+    }
+
+    // Sends MAV_CMD_COMPONENT_ARM_DISARM and completes once the autopilot's
+    // heartbeat, received after the command was accepted, reports the new
+    // state. Heartbeats from ground stations, other components and those
+    // buffered before the command are ignored.
+    fn set_armed(&mut self, armed: bool) -> Future<(), ()> {
         let (tx, future) = Future::<(), ()>::pair();
 
         let mut conn = self.connection.borrow_mut();
 
-        let mut ack = false;
-        let mut armed = false;
-
-        conn.complete(tx,
-                      Box::new(move |msg| {
-                          if let DkMessage::COMMAND_ACK(data) = msg {
-                              ack = ack || data.command == 400;
-                          } else if let DkMessage::HEARTBEAT(data) = msg {
-                              armed = armed || ((data.base_mode & 128) != 0);
-                          }
-                          ack && armed
-                      }));
+        let autopilot = self.autopilot;
+        let mut tx = Some(tx);
+        let mut accepted = false;
+        conn.watch_with_header(Box::new(move |header, msg| {
+            match msg {
+                DkMessage::COMMAND_ACK(ref data) if data.command == 400 => {
+                    if data.result != MAV_RESULT_ACCEPTED {
+                        if let Some(tx) = tx.take() {
+                            tx.fail(());
+                        }
+                        return true;
+                    }
+                    accepted = true;
+                    false
+                }
+                DkMessage::HEARTBEAT(ref data) if accepted &&
+                                                  data.autopilot != MAV_AUTOPILOT_INVALID &&
+                                                  autopilot.map_or(true, |ids| {
+                                                      ids == (header.system_id, header.component_id)
+                                                  }) => {
+                    if ((data.base_mode & 128) != 0) != armed {
+                        return false;
+                    }
+                    if let Some(tx) = tx.take() {
+                        tx.complete(());
+                    }
+                    true
+                }
+                _ => false,
+            }
+        }));
 
         conn.send(DkMessage::COMMAND_LONG(COMMAND_LONG_DATA {
-            target_system: 0,
-            target_component: 0,
+            target_system: conn.target_system,
+            target_component: conn.target_component,
             command: 400,
             confirmation: 0,
-            param1: 1.0,
+            param1: if armed { 1.0 } else { 0.0 },
             param2: 0.0,
             param3: 0.0,
             param4: 0.0,
@@ -381,8 +1754,8 @@ impl Vehicle {
         }));
 
         conn.send(DkMessage::COMMAND_LONG(COMMAND_LONG_DATA {
-            target_system: 0,
-            target_component: 0,
+            target_system: conn.target_system,
+            target_component: conn.target_component,
             command: 22,
             confirmation: 0,
             param1: 0.0,
@@ -412,8 +1785,8 @@ impl Vehicle {
         }));
 
         conn.send(DkMessage::COMMAND_LONG(COMMAND_LONG_DATA {
-            target_system: 0,
-            target_component: 0,
+            target_system: conn.target_system,
+            target_component: conn.target_component,
             command: 178,
             confirmation: 0,
             param1: 1.0,
@@ -454,8 +1827,8 @@ impl Vehicle {
 
         conn.send(DkMessage::SET_POSITION_TARGET_LOCAL_NED(SET_POSITION_TARGET_LOCAL_NED_DATA {
             time_boot_ms: 0,
-            target_system: 0,
-            target_component: 0,
+            target_system: conn.target_system,
+            target_component: conn.target_component,
             coordinate_frame: 1,
             type_mask: 0b0000_111_111_111_000,
             x: target.x,
@@ -474,6 +1847,191 @@ impl Vehicle {
         future
     }
 
+    // Flies to a global position and completes once the vehicle is within
+    // `radius` meters of it, measured along the WGS84 ellipsoid and
+    // vertically. Accepts a `LocationGlobal` (altitude above sea level),
+    // `LocationGlobalRelative` (above home) or a `GlobalTarget` with any
+    // `AltitudeFrame`. Height above terrain comes from TERRAIN_REPORT, which
+    // ArduPilot only sends with terrain following enabled; until one arrives,
+    // terrain targets are judged on horizontal distance alone.
+    pub fn goto_global<T: Into<GlobalTarget>>(&mut self, target: T, radius: f32) -> Future<(), ()> {
+        let target = target.into();
+        let (tx, future) = Future::<(), ()>::pair();
+
+        let mut conn = self.connection.borrow_mut();
+
+        let mut terrain_height = None;
+        conn.complete(tx,
+                      Box::new(move |msg| {
+                          match msg {
+                              DkMessage::TERRAIN_REPORT(data) => {
+                                  terrain_height = Some(data.current_height as f64);
+                                  false
+                              }
+                              DkMessage::GLOBAL_POSITION_INT(data) => {
+                                  let horizontal = vincenty(data.lat, data.lon, target.lat, target.lon);
+                                  let vertical = match target.frame {
+                                      AltitudeFrame::Amsl => data.alt as f64 / 1000.0 - target.alt as f64,
+                                      AltitudeFrame::Relative => {
+                                          data.relative_alt as f64 / 1000.0 - target.alt as f64
+                                      }
+                                      AltitudeFrame::Terrain => {
+                                          terrain_height.map_or(0.0, |x| x - target.alt as f64)
+                                      }
+                                  };
+                                  (horizontal.powi(2) + vertical.powi(2)).sqrt() <= radius as f64
+                              }
+                              _ => false,
+                          }
+                      }));
+
+        let outbox = conn.outbox();
+        outbox.send(position_target(&outbox, &target));
+
+        future
+    }
+
+    // Commands a velocity in meters per second and, if given, a yaw rate in
+    // radians per second. The vehicle must be in GUIDED mode. ArduCopter
+    // stops after 3 seconds without a new setpoint; see `stream_velocity`.
+    pub fn send_velocity(&mut self, frame: VelocityFrame, velocity: Velocity, yaw_rate: Option<f32>) {
+        let outbox = self.connection.borrow().outbox();
+        outbox.send(velocity_target(&outbox, frame, velocity, yaw_rate));
+    }
+
+    // Like `send_velocity`, but resends the setpoint at `rate_hz` until the
+    // returned stream is dropped. Use `VelocityStream::set` to change it.
+    // Fails if the rate is not positive or above `MAX_SETPOINT_RATE_HZ`.
+    pub fn stream_velocity(&mut self,
+                           frame: VelocityFrame,
+                           velocity: Velocity,
+                           yaw_rate: Option<f32>,
+                           rate_hz: f32)
+                           -> Result<VelocityStream, String> {
+        let outbox = self.connection.borrow().outbox();
+        VelocityStream::start(outbox, frame, velocity, yaw_rate, rate_hz)
+    }
+
+    pub fn set_fence_enabled(&mut self, enabled: bool) -> Future<(), ()> {
+        let (tx, future) = Future::<(), ()>::pair();
+
+        let mut conn = self.connection.borrow_mut();
+
+        conn.complete(tx, Box::new(move |msg| {
+            match msg {
+                DkMessage::COMMAND_ACK(data) => {
+                    data.command == 207
+                }
+                _ => false
+            }
+        }));
+
+        conn.send(DkMessage::COMMAND_LONG(COMMAND_LONG_DATA {
+            target_system: conn.target_system,
+            target_component: conn.target_component,
+            command: 207,
+            confirmation: 0,
+            param1: if enabled { 1.0 } else { 0.0 },
+            param2: 0.0,
+            param3: 0.0,
+            param4: 0.0,
+            param5: 0.0,
+            param6: 0.0,
+            param7: 0.0,
+        }));
+
+        future
+    }
+
+    // Downloads the fence with FENCE_FETCH_POINT. See `fence` for the layout
+    // this supports.
+    pub fn fetch_fence(&mut self) -> Future<Fence, ()> {
+        let mut conn = self.connection.borrow_mut();
+        points::download(&mut conn,
+                         fence_protocol(),
+                         self.parameters.encoding,
+                         |points| Fence::from_points(&points))
+    }
+
+    // Replaces the fence, setting FENCE_TOTAL and then writing each point.
+    // ArduPilot may reject changes while the fence is enabled, so disable it
+    // first with `set_fence_enabled`.
+    pub fn upload_fence(&mut self, fence: &Fence) -> Future<(), ()> {
+        let points = match fence.to_points() {
+            Ok(points) => points,
+            Err(..) => return Future::error(()),
+        };
+        let total = self.points_total("FENCE_TOTAL", points.len());
+        let mut conn = self.connection.borrow_mut();
+        points::upload(&mut conn, fence_protocol(), self.parameters.encoding, total, points)
+    }
+
+    // Downloads the rally points with RALLY_FETCH_POINT.
+    pub fn fetch_rally_points(&mut self) -> Future<Vec<RallyPoint>, ()> {
+        let mut conn = self.connection.borrow_mut();
+        points::download(&mut conn, rally_protocol(), self.parameters.encoding, Some)
+    }
+
+    // Replaces the rally points, setting RALLY_TOTAL and then writing each
+    // point.
+    pub fn upload_rally_points(&mut self, rally_points: Vec<RallyPoint>) -> Future<(), ()> {
+        let total = self.points_total("RALLY_TOTAL", rally_points.len());
+        let mut conn = self.connection.borrow_mut();
+        points::upload(&mut conn,
+                       rally_protocol(),
+                       self.parameters.encoding,
+                       total,
+                       rally_points)
+    }
+
+    // A point count for the parameter `name`, of the parameter's type if it
+    // has been received. ArduPilot stores point counts as INT8.
+    fn points_total(&self, name: &str, count: usize) -> ParamValue {
+        match self.parameters.get(name) {
+            Some(current) => current.with_value(count as f32),
+            None => ParamValue::I8(count as i8),
+        }
+    }
+
+    // Sends COMMAND_LONG to one component of the vehicle, such as
+    // `MAV_COMP_ID_CAMERA` or `MAV_COMP_ID_GIMBAL`, and completes when the
+    // command is acknowledged. Other helpers address the connection's
+    // `target_component`.
+    pub fn command_component(&mut self,
+                             component_id: u8,
+                             command: u16,
+                             params: [f32; 7])
+                             -> Future<(), ()> {
+        let (tx, future) = Future::<(), ()>::pair();
+
+        let mut conn = self.connection.borrow_mut();
+
+        conn.complete(tx, Box::new(move |msg| {
+            match msg {
+                DkMessage::COMMAND_ACK(data) => {
+                    data.command == command
+                }
+                _ => false
+            }
+        }));
+
+        conn.send(DkMessage::COMMAND_LONG(COMMAND_LONG_DATA {
+            target_system: conn.target_system,
+            target_component: component_id,
+            command: command,
+            confirmation: 0,
+            param1: params[0],
+            param2: params[1],
+            param3: params[2],
+            param4: params[3],
+            param5: params[4],
+            param6: params[5],
+            param7: params[6],
+        }));
+
+        future
+    }
+
     pub fn wait_alt(&mut self, target_alt: f32) -> Future<(), ()> {
         let (tx, future) = Future::<(), ()>::pair();
 
@@ -497,3 +2055,36 @@ impl Vehicle {
         }
     }
 }
+
+#[test]
+fn test_modes() {
+    assert_eq!(VehicleMode::from_name("guided_nogps"), Some(VehicleMode::GUIDED_NOGPS));
+    assert_eq!(VehicleMode::GUIDED.custom_mode(Firmware::Copter), Some(4));
+    assert_eq!(VehicleMode::GUIDED.custom_mode(Firmware::Plane), Some(15));
+    assert_eq!(VehicleMode::POSHOLD.custom_mode(Firmware::Rover), None);
+    assert_eq!(VehicleMode::from_custom_mode(Firmware::Rover, 4), Some(VehicleMode::HOLD));
+    assert_eq!(Firmware::from_mav_type(2), Some(Firmware::Copter));
+}
+
+#[test]
+fn test_vincenty() {
+    // Flinders Peak to Buninyong, 54972.271 m before rounding to 1e-7
+    // degrees.
+    assert!((vincenty(-379510334, 1444248679, -376528211, 1439264955) - 54972.275).abs() < 0.01);
+    // A degree along the equator and along a meridian.
+    assert!((vincenty(0, 0, 0, 10000000) - 111319.491).abs() < 0.01);
+    assert!((vincenty(0, 0, 10000000, 0) - 110574.389).abs() < 0.01);
+    assert_eq!(vincenty(473977420, 85455940, 473977420, 85455940), 0.0);
+    // 0.2 degrees along the equator, across the antimeridian.
+    assert!((vincenty(0, 1799000000, 0, -1799000000) - 22263.898).abs() < 0.01);
+    assert!((vincenty(0, -1799000000, 0, 1799000000) - 22263.898).abs() < 0.01);
+}
+
+#[test]
+fn test_same_value() {
+    assert!(same_value(ParamValue::I32(100000000), ParamValue::I32(100000000)));
+    assert!(!same_value(ParamValue::I32(100000000), ParamValue::I32(100000008)));
+    assert!(!same_value(ParamValue::I32(16777217), ParamValue::F32(16777216.0)));
+    assert!(same_value(ParamValue::U8(3), ParamValue::F32(3.0)));
+    assert!(!same_value(ParamValue::F32(0.1), ParamValue::F32(0.1000001)));
+}